@@ -0,0 +1,155 @@
+use crate::package::{DependencyType, Package};
+use crate::package_graph::PackageGraph;
+use clean_path::Clean;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use rustc_hash::FxHashSet;
+use std::collections::{BTreeMap, VecDeque};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum AffectedPropagation {
+    // Dependents are not affected.
+    None,
+
+    // Direct dependents are affected, but not their dependents.
+    Direct,
+
+    // Direct and transitive dependents are affected.
+    #[default]
+    Transitive,
+}
+
+// How an affected package propagates to its dependents,
+// based on the type of dependency.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AffectedRules {
+    pub development: AffectedPropagation,
+    pub optional: AffectedPropagation,
+    pub peer: AffectedPropagation,
+    pub production: AffectedPropagation,
+}
+
+impl AffectedRules {
+    pub fn get(&self, dep_type: DependencyType) -> AffectedPropagation {
+        match dep_type {
+            DependencyType::Development => self.development,
+            DependencyType::Optional => self.optional,
+            DependencyType::Peer => self.peer,
+            DependencyType::Production => self.production,
+        }
+    }
+}
+
+impl Default for AffectedRules {
+    // Development dependencies are not shipped with a package,
+    // so changes only affect the package that depends on them.
+    fn default() -> Self {
+        Self {
+            development: AffectedPropagation::Direct,
+            optional: AffectedPropagation::Transitive,
+            peer: AffectedPropagation::Transitive,
+            production: AffectedPropagation::Transitive,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AffectedReason {
+    // A file owned by the package has changed. The path is
+    // relative to the workspace root.
+    ChangedFile(PathBuf),
+
+    // A dependency of the package is affected.
+    Dependency {
+        name: String,
+        dep_type: DependencyType,
+    },
+}
+
+impl PackageGraph {
+    // Returns the package that owns the file, which is the package with the
+    // deepest root that contains it. Falls back to the root package when
+    // no workspace package matches, or `None` if outside the workspace.
+    pub fn get_owning_package<T: AsRef<Path>>(&self, file: T) -> Option<&Package> {
+        let file = self.root.join(file.as_ref()).clean();
+
+        if !file.starts_with(&self.root) {
+            return None;
+        }
+
+        self.packages
+            .values()
+            .filter(|package| file.starts_with(&package.root))
+            .max_by_key(|package| package.root.components().count())
+            .or(Some(&self.root_package))
+    }
+
+    // Returns all packages affected by the changed files, mapped to the
+    // reasons why they are affected. Files are relative to the workspace
+    // root, or absolute. Affected packages are expanded to their dependents
+    // based on the propagation rule of each dependency type.
+    pub fn get_affected_packages<I, T>(
+        &self,
+        files: I,
+        rules: &AffectedRules,
+    ) -> BTreeMap<String, Vec<AffectedReason>>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<Path>,
+    {
+        let mut affected: BTreeMap<String, Vec<AffectedReason>> = BTreeMap::new();
+        let mut propagating = FxHashSet::default();
+        let mut queue = VecDeque::new();
+
+        for file in files {
+            let file = file.as_ref();
+            let Some(package) = self.get_owning_package(file) else {
+                continue;
+            };
+
+            let name = self.graph[package.node_index].to_owned();
+            let relative_file = self
+                .root
+                .join(file)
+                .clean()
+                .strip_prefix(&self.root)
+                .map(|path| path.to_path_buf())
+                .unwrap_or_else(|_| file.to_path_buf());
+
+            affected
+                .entry(name)
+                .or_default()
+                .push(AffectedReason::ChangedFile(relative_file));
+
+            if propagating.insert(package.node_index) {
+                queue.push_back(package.node_index);
+            }
+        }
+
+        while let Some(node) = queue.pop_front() {
+            for edge in self.graph.edges_directed(node, Direction::Incoming) {
+                let dependent = edge.source();
+                let propagation = rules.get(*edge.weight());
+
+                if propagation == AffectedPropagation::None {
+                    continue;
+                }
+
+                affected
+                    .entry(self.graph[dependent].to_owned())
+                    .or_default()
+                    .push(AffectedReason::Dependency {
+                        name: self.graph[node].to_owned(),
+                        dep_type: *edge.weight(),
+                    });
+
+                if propagation == AffectedPropagation::Transitive && propagating.insert(dependent) {
+                    queue.push_back(dependent);
+                }
+            }
+        }
+
+        affected
+    }
+}
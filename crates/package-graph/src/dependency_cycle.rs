@@ -0,0 +1,218 @@
+use crate::package::DependencyType;
+use crate::package_graph::{PackageGraph, PackageGraphType};
+use crate::package_graph_error::PackageGraphError;
+use petgraph::algo::tarjan_scc;
+use petgraph::graph::NodeIndex;
+use petgraph::Direction;
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::collections::BTreeSet;
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct DependencyCycle {
+    // Package names in the order they depend on each other. The last
+    // package depends on the first package to close the cycle.
+    pub packages: Vec<String>,
+
+    // Type of each edge, where `edges[i]` connects `packages[i]`
+    // to the next package in the cycle.
+    pub edges: Vec<DependencyType>,
+}
+
+impl DependencyCycle {
+    // Cycles that only contain development and peer dependencies do not
+    // affect runtime code, and can usually be treated as warnings.
+    pub fn is_dev_only(&self) -> bool {
+        self.edges.iter().all(is_dev_edge)
+    }
+}
+
+impl fmt::Display for DependencyCycle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, (name, edge)) in self.packages.iter().zip(&self.edges).enumerate() {
+            if index > 0 {
+                write!(f, " ")?;
+            }
+
+            write!(f, "{name} --{}-->", edge.field_name())?;
+        }
+
+        if let Some(first) = self.packages.first() {
+            write!(f, " {first}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl PackageGraph {
+    // Returns every elementary dependency cycle between workspace packages,
+    // each starting from its package with the lowest name, and sorted by name.
+    pub fn detect_cycles(&self) -> Vec<DependencyCycle> {
+        let graph = self.to_filtered_graph(&[
+            DependencyType::Production,
+            DependencyType::Optional,
+            DependencyType::Development,
+            DependencyType::Peer,
+        ]);
+
+        let mut cycles = find_elementary_cycles(&graph)
+            .into_iter()
+            .map(|nodes| {
+                let edges = nodes
+                    .iter()
+                    .enumerate()
+                    .map(|(index, node)| {
+                        let next = nodes[(index + 1) % nodes.len()];
+
+                        // When a package depends on another through multiple fields,
+                        // report the strongest one so that the cycle isn't downgraded
+                        graph
+                            .edges_connecting(*node, next)
+                            .map(|edge| *edge.weight())
+                            .min_by_key(is_dev_edge)
+                            .unwrap()
+                    })
+                    .collect();
+
+                DependencyCycle {
+                    packages: nodes.iter().map(|node| graph[*node].to_owned()).collect(),
+                    edges,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        cycles.sort_by(|a, b| a.packages.cmp(&b.packages));
+        cycles
+    }
+
+    // Errors for the first dependency cycle found. When `dev_cycles_as_warnings`
+    // is enabled, cycles made only of development and peer dependencies are
+    // returned instead of erroring.
+    pub fn validate_cycles(
+        &self,
+        dev_cycles_as_warnings: bool,
+    ) -> Result<Vec<DependencyCycle>, PackageGraphError> {
+        let mut warnings = vec![];
+
+        for cycle in self.detect_cycles() {
+            if dev_cycles_as_warnings && cycle.is_dev_only() {
+                warnings.push(cycle);
+            } else {
+                return Err(PackageGraphError::DependencyCycle(cycle));
+            }
+        }
+
+        Ok(warnings)
+    }
+}
+
+fn is_dev_edge(edge: &DependencyType) -> bool {
+    matches!(edge, DependencyType::Development | DependencyType::Peer)
+}
+
+// Enumerates elementary cycles with Johnson's algorithm. Nodes are visited in
+// name order, and each cycle is found once, rotated to start at the node with
+// the lowest name.
+fn find_elementary_cycles(graph: &PackageGraphType) -> Vec<Vec<NodeIndex>> {
+    let mut nodes = graph.node_indices().collect::<Vec<_>>();
+    nodes.sort_by(|a, b| graph[*a].cmp(&graph[*b]));
+
+    let mut cycles = vec![];
+
+    for (rank, start) in nodes.iter().enumerate() {
+        // Only search the component of the start node within the subgraph
+        // of nodes that haven't been a start node yet
+        let remaining = nodes[rank..].iter().copied().collect::<FxHashSet<_>>();
+        let subgraph = graph.filter_map(
+            |index, _| remaining.contains(&index).then_some(index),
+            |_, edge| Some(*edge),
+        );
+
+        let Some(component) = tarjan_scc(&subgraph)
+            .into_iter()
+            .map(|component| {
+                component
+                    .into_iter()
+                    .map(|index| subgraph[index])
+                    .collect::<FxHashSet<_>>()
+            })
+            .find(|component| component.contains(start))
+        else {
+            continue;
+        };
+
+        let mut finder = CycleFinder {
+            graph,
+            members: component,
+            blocked: FxHashSet::default(),
+            blocked_by: FxHashMap::default(),
+            stack: vec![],
+            cycles: vec![],
+        };
+
+        finder.circuit(*start, *start);
+        cycles.extend(finder.cycles);
+    }
+
+    cycles
+}
+
+struct CycleFinder<'graph> {
+    graph: &'graph PackageGraphType,
+    members: FxHashSet<NodeIndex>,
+    blocked: FxHashSet<NodeIndex>,
+    blocked_by: FxHashMap<NodeIndex, FxHashSet<NodeIndex>>,
+    stack: Vec<NodeIndex>,
+    cycles: Vec<Vec<NodeIndex>>,
+}
+
+impl CycleFinder<'_> {
+    fn circuit(&mut self, node: NodeIndex, start: NodeIndex) -> bool {
+        let mut found = false;
+
+        self.stack.push(node);
+        self.blocked.insert(node);
+
+        for next in self.successors(node) {
+            if next == start {
+                self.cycles.push(self.stack.clone());
+                found = true;
+            } else if !self.blocked.contains(&next) && self.circuit(next, start) {
+                found = true;
+            }
+        }
+
+        if found {
+            self.unblock(node);
+        } else {
+            for next in self.successors(node) {
+                self.blocked_by.entry(next).or_default().insert(node);
+            }
+        }
+
+        self.stack.pop();
+
+        found
+    }
+
+    fn unblock(&mut self, node: NodeIndex) {
+        self.blocked.remove(&node);
+
+        if let Some(dependents) = self.blocked_by.remove(&node) {
+            for dependent in dependents {
+                if self.blocked.contains(&dependent) {
+                    self.unblock(dependent);
+                }
+            }
+        }
+    }
+
+    // Parallel edges are collapsed, so that each cycle is only found once.
+    fn successors(&self, node: NodeIndex) -> BTreeSet<NodeIndex> {
+        self.graph
+            .neighbors_directed(node, Direction::Outgoing)
+            .filter(|next| self.members.contains(next))
+            .collect()
+    }
+}
@@ -0,0 +1,112 @@
+use crate::package_graph::PackageGraph;
+use nodejs_package_json::Range;
+use std::collections::BTreeMap;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EngineIntersection {
+    // Pairs of packages whose ranges do not overlap, sorted by name.
+    pub conflicts: Vec<(String, String)>,
+
+    // Packages left out of the range to resolve the conflicts, sorted by name.
+    pub excluded: Vec<String>,
+
+    // Tightest range that satisfies all packages that are not excluded, or
+    // `None` if no package defines the engine. Ranges that overlap in pairs
+    // can still have nothing in common, like `1 || 2`, `2 || 3` and `1 || 3`,
+    // which is also `None`.
+    pub range: Option<Range>,
+
+    // Values that are not a version range, like a dist-tag, keyed by package name.
+    pub unparseable: BTreeMap<String, String>,
+}
+
+impl PackageGraph {
+    // Intersects the engine range of the root and every workspace package.
+    // Every pair of packages is compared, so the result does not depend on the
+    // order of packages. To resolve conflicts, the package with the most
+    // conflicts is excluded first, until the remaining packages all overlap.
+    pub fn intersect_engines(&self, engine: &str) -> EngineIntersection {
+        let mut result = EngineIntersection::default();
+        let mut ranges = vec![];
+
+        for package in [&self.root_package]
+            .into_iter()
+            .chain(self.packages.values())
+        {
+            let Some(value) = package
+                .manifest
+                .engines
+                .as_ref()
+                .and_then(|engines| engines.get(engine))
+            else {
+                continue;
+            };
+
+            let name = package
+                .manifest
+                .name
+                .clone()
+                .unwrap_or_else(|| "(root)".into());
+
+            match Range::parse(value.to_string()) {
+                Ok(range) => ranges.push((name, range)),
+                Err(_) => {
+                    result.unparseable.insert(name, value.to_string());
+                }
+            };
+        }
+
+        ranges.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut conflicting = vec![vec![]; ranges.len()];
+
+        for (i, (a_name, a_range)) in ranges.iter().enumerate() {
+            for (j, (b_name, b_range)) in ranges.iter().enumerate().skip(i + 1) {
+                if !a_range.intersects(b_range) {
+                    conflicting[i].push(j);
+                    conflicting[j].push(i);
+                    result
+                        .conflicts
+                        .push((a_name.to_owned(), b_name.to_owned()));
+                }
+            }
+        }
+
+        let mut excluded = vec![false; ranges.len()];
+
+        loop {
+            let count = |index: usize| {
+                conflicting[index]
+                    .iter()
+                    .filter(|other| !excluded[**other])
+                    .count()
+            };
+
+            // Ties are broken by name, as `max_by_key` returns the last maximum
+            let Some(index) = (0..ranges.len())
+                .rev()
+                .filter(|index| !excluded[*index] && count(*index) > 0)
+                .max_by_key(|index| count(*index))
+            else {
+                break;
+            };
+
+            excluded[index] = true;
+            result.excluded.push(ranges[index].0.clone());
+        }
+
+        result.excluded.sort();
+
+        let mut included = ranges
+            .into_iter()
+            .zip(excluded)
+            .filter(|(_, excluded)| !excluded)
+            .map(|((_, range), _)| range);
+
+        result.range = included
+            .next()
+            .and_then(|first| included.try_fold(first, |current, range| current.intersect(&range)));
+
+        result
+    }
+}
@@ -1,7 +1,18 @@
+mod affected;
+mod dependency_cycle;
+mod engines;
 mod package;
 mod package_graph;
 mod package_graph_error;
+mod package_selector;
+mod publish;
+mod topology;
+mod traversal;
 
+pub use affected::*;
+pub use dependency_cycle::*;
+pub use engines::*;
 pub use package::*;
 pub use package_graph::*;
 pub use package_graph_error::*;
+pub use package_selector::*;
@@ -4,6 +4,8 @@ use petgraph::graph::NodeIndex;
 use starbase_utils::json;
 use std::path::{Path, PathBuf};
 
+pub use nodejs_package_json::DependencyType;
+
 #[derive(Debug)]
 pub struct Package {
     pub manifest: PackageJson,
@@ -41,11 +43,3 @@ impl Package {
             .ok_or_else(|| PackageGraphError::MissingPackageName(self.root.clone()))
     }
 }
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum DependencyType {
-    Development,
-    Production,
-    Peer,
-    Optional,
-}
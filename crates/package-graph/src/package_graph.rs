@@ -2,26 +2,31 @@ use crate::package::{DependencyType, Package};
 use crate::package_graph_error::PackageGraphError;
 use clean_path::Clean;
 use nodejs_package_json::{
-    DependenciesMap, PackageJson, Version, VersionProtocol, WorkspaceProtocol, WorkspacesField,
+    parse_package_manager, DependenciesMap, PackageJson, Range, Version, VersionProtocol,
+    WorkspaceProtocol, WorkspacesField,
 };
 use nodejs_package_managers::{pnpm::PnpmWorkspaceYaml, PackageManager};
 use petgraph::graph::DiGraph;
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
+use rustc_hash::FxHashMap;
 use starbase_utils::{glob, json, yaml};
 use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 pub type PackageGraphType = DiGraph<String, DependencyType>;
 
 pub struct PackageGraph {
+    // Keyed by catalog name, with `default` for the default catalog.
+    pub catalogs: BTreeMap<String, DependenciesMap<VersionProtocol>>,
     pub cwd: PathBuf,
     pub manager: PackageManager,
     pub packages: BTreeMap<String, Package>,
     pub root: PathBuf,
     pub root_package: Package,
 
-    graph: PackageGraphType,
+    pub(crate) graph: PackageGraphType,
     package_globs: Vec<String>,
 }
 
@@ -43,8 +48,13 @@ impl PackageGraph {
 
         let root_manifest: PackageJson = json::read_file(root.join("package.json"))?;
 
+        // Prefer the declared package manager over lockfile heuristics
+        let package_manager =
+            Self::find_declared_package_manager(&root_manifest).unwrap_or(package_manager);
+
         // Extract workspaces globs
         let mut package_globs = vec![];
+        let mut catalogs = BTreeMap::new();
 
         if package_manager == PackageManager::Pnpm {
             let ws_file = root.join("pnpm-workspace.yaml");
@@ -52,6 +62,16 @@ impl PackageGraph {
             if ws_file.exists() {
                 let ws: PnpmWorkspaceYaml = yaml::read_file(ws_file)?;
 
+                if let Some(catalog) = ws.get_catalog(None) {
+                    catalogs.insert("default".to_owned(), parse_catalog(catalog)?);
+                }
+
+                for (name, catalog) in &ws.catalogs {
+                    if name != "default" {
+                        catalogs.insert(name.to_owned(), parse_catalog(catalog)?);
+                    }
+                }
+
                 package_globs = ws.packages;
             }
         } else if let Some(workspaces) = &root_manifest.workspaces {
@@ -62,6 +82,7 @@ impl PackageGraph {
         }
 
         Ok(PackageGraph {
+            catalogs,
             cwd: working_dir.to_owned(),
             graph: DiGraph::new(),
             manager: package_manager,
@@ -111,6 +132,30 @@ impl PackageGraph {
         None
     }
 
+    pub fn find_declared_package_manager(manifest: &PackageJson) -> Option<PackageManager> {
+        if let Ok(Some(field)) = manifest.parse_package_manager() {
+            return Some(field.manager);
+        }
+
+        let engine = manifest
+            .dev_engines
+            .as_ref()?
+            .package_manager
+            .as_ref()?
+            .to_list()
+            .into_iter()
+            .next()?;
+
+        let version = match engine.version.as_ref().filter(|range| !range.is_any()) {
+            Some(range) => range.min_version()?,
+            // Yarn v1 and v2+ can't be determined without a version
+            None if engine.name == "yarn" => return None,
+            None => Version::new(0, 0, 0),
+        };
+
+        parse_package_manager(&engine.name, &version).ok()
+    }
+
     pub fn is_workspaces_enabled(&self) -> bool {
         !self.package_globs.is_empty() && !self.packages.is_empty()
     }
@@ -153,7 +198,7 @@ impl PackageGraph {
         let mut graph = DiGraph::new();
 
         // Name is optional for the workspace root
-        graph.add_node(if self.is_workspaces_enabled() {
+        self.root_package.node_index = graph.add_node(if self.is_workspaces_enabled() {
             self.root_package
                 .manifest
                 .name
@@ -200,24 +245,48 @@ impl PackageGraph {
             }
         };
 
+        let any_version = VersionProtocol::Requirement(Range::any());
+
         let add_edges = |graph: &mut PackageGraphType,
                          package: &Package,
                          deps: &DependenciesMap<VersionProtocol>,
-                         dep_type: DependencyType| {
+                         dep_type: DependencyType|
+         -> Result<(), PackageGraphError> {
             for (name, version) in deps {
+                // catalog:name
+                let version = match version {
+                    VersionProtocol::Catalog(catalog) => self
+                        .get_catalog_version(catalog.as_deref(), name)
+                        .map_err(|_| PackageGraphError::UnresolvedCatalogReference {
+                            catalog: catalog.as_deref().unwrap_or("default").to_owned(),
+                            dependency: name.to_owned(),
+                            package: graph[package.node_index].to_owned(),
+                        })?,
+                    _ => version,
+                };
+
+                // npm:package@version
+                let (name, version) = match version {
+                    VersionProtocol::Alias {
+                        package,
+                        requirement,
+                    } => (package, requirement.as_deref().unwrap_or(&any_version)),
+                    _ => (name, version),
+                };
+
                 match version {
                     // npm
-                    VersionProtocol::Requirement(req) => {
+                    VersionProtocol::Range(range) | VersionProtocol::Requirement(range) => {
                         if let Some(dep_package) = self.packages.get(name) {
                             if
                             // *
-                            req.comparators.is_empty()
-                                // ~, ^, etc
+                            range.is_any()
+                                // ~, ^, ||, etc
                                 || dep_package
                                     .manifest
                                     .version
                                     .as_ref()
-                                    .is_some_and(|ver| req.matches(ver))
+                                    .is_some_and(|ver| range.satisfies(ver))
                             {
                                 graph.add_edge(
                                     package.node_index,
@@ -282,6 +351,8 @@ impl PackageGraph {
                     _ => {}
                 };
             }
+
+            Ok(())
         };
 
         let mut packages = vec![&self.root_package];
@@ -289,19 +360,19 @@ impl PackageGraph {
 
         for package in packages {
             if let Some(deps) = &package.manifest.dependencies {
-                add_edges(&mut graph, package, deps, DependencyType::Production);
+                add_edges(&mut graph, package, deps, DependencyType::Production)?;
             }
 
             if let Some(deps) = &package.manifest.dev_dependencies {
-                add_edges(&mut graph, package, deps, DependencyType::Development);
+                add_edges(&mut graph, package, deps, DependencyType::Development)?;
             }
 
             if let Some(deps) = &package.manifest.peer_dependencies {
-                add_edges(&mut graph, package, deps, DependencyType::Peer);
+                add_edges(&mut graph, package, deps, DependencyType::Peer)?;
             }
 
             if let Some(deps) = &package.manifest.optional_dependencies {
-                add_edges(&mut graph, package, deps, DependencyType::Optional);
+                add_edges(&mut graph, package, deps, DependencyType::Optional)?;
             }
         }
 
@@ -310,6 +381,54 @@ impl PackageGraph {
         Ok(())
     }
 
+    pub fn get_catalog_version(
+        &self,
+        catalog: Option<&str>,
+        name: &str,
+    ) -> Result<&VersionProtocol, PackageGraphError> {
+        let catalog = catalog.unwrap_or("default");
+
+        self.catalogs
+            .get(catalog)
+            .ok_or_else(|| PackageGraphError::UnknownCatalog(catalog.to_owned()))?
+            .get(name)
+            .ok_or_else(|| PackageGraphError::MissingCatalogEntry {
+                catalog: catalog.to_owned(),
+                name: name.to_owned(),
+            })
+    }
+
+    // Returns a copy of the package's manifest, with all `catalog:`
+    // references replaced with the version from the catalog.
+    pub fn resolve_catalog_references(&self, name: &str) -> Result<PackageJson, PackageGraphError> {
+        let package = self
+            .packages
+            .get(name)
+            .ok_or_else(|| PackageGraphError::UnknownPackage(name.to_owned()))?;
+
+        let mut manifest = package.manifest.clone();
+
+        for deps in [
+            &mut manifest.dependencies,
+            &mut manifest.dev_dependencies,
+            &mut manifest.peer_dependencies,
+            &mut manifest.optional_dependencies,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            for (dep_name, version) in deps.iter_mut() {
+                if let VersionProtocol::Catalog(catalog) = version {
+                    *version = self
+                        .get_catalog_version(catalog.as_deref(), dep_name)?
+                        .to_owned();
+                }
+            }
+        }
+
+        Ok(manifest)
+    }
+
     pub fn dependencies_of(
         &self,
         name: &str,
@@ -345,9 +464,10 @@ impl PackageGraph {
         let deps = self
             .graph
             .edges_directed(package.node_index, Direction::Incoming)
+            .filter(|edge| edge.source() != self.root_package.node_index)
             .map(|edge| {
                 (
-                    self.graph.node_weight(edge.target()).unwrap().to_owned(),
+                    self.graph.node_weight(edge.source()).unwrap().to_owned(),
                     edge.weight().to_owned(),
                 )
             })
@@ -360,3 +480,15 @@ impl PackageGraph {
         format!("{:?}", petgraph::dot::Dot::new(&self.graph))
     }
 }
+
+fn parse_catalog(
+    catalog: &FxHashMap<String, String>,
+) -> Result<DependenciesMap<VersionProtocol>, PackageGraphError> {
+    let mut deps = DependenciesMap::new();
+
+    for (name, version) in catalog {
+        deps.insert(name.to_owned(), VersionProtocol::from_str(version)?);
+    }
+
+    Ok(deps)
+}
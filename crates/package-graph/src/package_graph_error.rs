@@ -1,3 +1,5 @@
+use crate::dependency_cycle::DependencyCycle;
+use nodejs_package_json::VersionProtocolError;
 use starbase_utils::fs::FsError;
 use starbase_utils::glob::GlobError;
 use starbase_utils::json::JsonError;
@@ -8,6 +10,27 @@ use thiserror::Error;
 #[derive(Debug, Error)]
 #[cfg_attr(feature = "miette", derive(miette::Diagnostic))]
 pub enum PackageGraphError {
+    #[error("Dependency cycle detected: {0}")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(
+            code(package_graph::dependency_cycle),
+            help("Remove one of the dependencies in the cycle to break it.")
+        )
+    )]
+    DependencyCycle(DependencyCycle),
+
+    #[error("Invalid package selector `{0}`.")]
+    #[cfg_attr(feature = "miette", diagnostic(code(package_graph::invalid_selector)))]
+    InvalidSelector(String),
+
+    #[error("Catalog `{catalog}` does not define a version for `{name}`.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_graph::missing_catalog_entry))
+    )]
+    MissingCatalogEntry { catalog: String, name: String },
+
     #[error("Package requires a `name` field. Missing in {0}.")]
     #[cfg_attr(
         feature = "miette",
@@ -15,10 +38,34 @@ pub enum PackageGraphError {
     )]
     MissingPackageName(PathBuf),
 
+    #[error("Package `{0}` requires a `version` field to replace `workspace:` references.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_graph::missing_package_version))
+    )]
+    MissingPackageVersion(String),
+
+    #[error("Unknown catalog `{0}`. Not defined in pnpm-workspace.yaml.")]
+    #[cfg_attr(feature = "miette", diagnostic(code(package_graph::unknown_catalog)))]
+    UnknownCatalog(String),
+
     #[error("Unknown package `{0}`. Not found in package graph.")]
     #[cfg_attr(feature = "miette", diagnostic(code(package_graph::unknown_package)))]
     UnknownPackage(String),
 
+    #[error(
+        "Package `{package}` depends on `{dependency}` from catalog `{catalog}`, but the catalog does not define it."
+    )]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_graph::unresolved_catalog_reference))
+    )]
+    UnresolvedCatalogReference {
+        catalog: String,
+        dependency: String,
+        package: String,
+    },
+
     #[error(transparent)]
     #[cfg_attr(feature = "miette", diagnostic(transparent))]
     Fs(#[from] FsError),
@@ -31,6 +78,10 @@ pub enum PackageGraphError {
     #[cfg_attr(feature = "miette", diagnostic(transparent))]
     Json(#[from] JsonError),
 
+    #[error(transparent)]
+    #[cfg_attr(feature = "miette", diagnostic(transparent))]
+    VersionProtocol(#[from] VersionProtocolError),
+
     #[error(transparent)]
     #[cfg_attr(feature = "miette", diagnostic(transparent))]
     Yaml(#[from] YamlError),
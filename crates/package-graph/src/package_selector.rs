@@ -0,0 +1,255 @@
+use crate::package::DependencyType;
+use crate::package_graph::PackageGraph;
+use crate::package_graph_error::PackageGraphError;
+use clean_path::Clean;
+use rustc_hash::FxHashSet;
+use starbase_utils::glob;
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::str::FromStr;
+
+// A pnpm-style `--filter` selector. All criteria that are defined
+// must match for a package to be selected.
+// https://pnpm.io/filtering
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PackageSelector {
+    // Git reference to select changed packages since, e.g. `[origin/main]`.
+    pub changed_since: Option<String>,
+
+    // Directory or directory glob, relative to the current working
+    // directory, e.g. `./packages/**` or `{packages/foo}`.
+    pub directory: Option<String>,
+
+    // Remove the selected packages from the result, e.g. `!foo`.
+    pub exclude: bool,
+
+    // Do not select the matched packages themselves through their
+    // dependencies, only the dependencies, e.g. `foo^...`.
+    pub exclude_self_from_dependencies: bool,
+
+    // Do not select the matched packages themselves through their
+    // dependents, only the dependents, e.g. `...^foo`.
+    pub exclude_self_from_dependents: bool,
+
+    // Also select dependencies of matched packages, e.g. `foo...`.
+    pub include_dependencies: bool,
+
+    // Also select dependents of matched packages, e.g. `...foo`.
+    pub include_dependents: bool,
+
+    // Package name or name glob, e.g. `foo` or `@scope/*`.
+    pub name: Option<String>,
+}
+
+impl PackageSelector {
+    // A matched package is selected itself unless every relationship
+    // that is followed excludes it, so `...^foo...` still selects `foo`.
+    fn includes_self(&self) -> bool {
+        let from_dependencies = self.include_dependencies && !self.exclude_self_from_dependencies;
+        let from_dependents = self.include_dependents && !self.exclude_self_from_dependents;
+
+        from_dependencies
+            || from_dependents
+            || !self.include_dependencies && !self.include_dependents
+    }
+}
+
+impl FromStr for PackageSelector {
+    type Err = PackageGraphError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut selector = PackageSelector::default();
+        let mut rest = value.trim();
+
+        if let Some(inner) = rest.strip_prefix('!') {
+            selector.exclude = true;
+            rest = inner;
+        }
+
+        // Both `...^foo` and `^...foo` are supported for dependents
+        if let Some(inner) = rest
+            .strip_prefix("...^")
+            .or_else(|| rest.strip_prefix("^..."))
+        {
+            selector.include_dependents = true;
+            selector.exclude_self_from_dependents = true;
+            rest = inner;
+        } else if let Some(inner) = rest.strip_prefix("...") {
+            selector.include_dependents = true;
+            rest = inner;
+        }
+
+        if let Some(inner) = rest.strip_suffix("^...") {
+            selector.include_dependencies = true;
+            selector.exclude_self_from_dependencies = true;
+            rest = inner;
+        } else if let Some(inner) = rest.strip_suffix("...") {
+            selector.include_dependencies = true;
+            rest = inner;
+        }
+
+        if let Some(inner) = rest.strip_suffix(']') {
+            let Some((inner, reference)) = inner.split_once('[') else {
+                return Err(PackageGraphError::InvalidSelector(value.to_owned()));
+            };
+
+            if reference.is_empty() {
+                return Err(PackageGraphError::InvalidSelector(value.to_owned()));
+            }
+
+            selector.changed_since = Some(reference.to_owned());
+            rest = inner;
+        }
+
+        if let Some(inner) = rest.strip_prefix('{') {
+            let Some(dir) = inner.strip_suffix('}') else {
+                return Err(PackageGraphError::InvalidSelector(value.to_owned()));
+            };
+
+            selector.directory = Some(dir.to_owned());
+        } else if rest.starts_with('.') {
+            selector.directory = Some(rest.to_owned());
+        } else if !rest.is_empty() {
+            selector.name = Some(rest.to_owned());
+        }
+
+        if selector.name.is_none()
+            && selector.directory.is_none()
+            && selector.changed_since.is_none()
+        {
+            return Err(PackageGraphError::InvalidSelector(value.to_owned()));
+        }
+
+        Ok(selector)
+    }
+}
+
+impl PackageGraph {
+    // Returns the names of all workspace packages matched by the selectors.
+    // When only exclusions are provided, they are removed from all packages,
+    // while no selectors at all select no packages.
+    // Changed files are used for `[ref]` selectors, and are relative to the
+    // workspace root, or absolute.
+    pub fn filter_packages<T: AsRef<Path>>(
+        &self,
+        selectors: &[PackageSelector],
+        changed_files: &[T],
+    ) -> Result<Vec<String>, PackageGraphError> {
+        let all_types = [
+            DependencyType::Production,
+            DependencyType::Development,
+            DependencyType::Peer,
+            DependencyType::Optional,
+        ];
+        let changed = changed_files
+            .iter()
+            .filter_map(|file| self.get_owning_package(file))
+            .filter_map(|package| package.manifest.name.as_deref())
+            .filter(|name| self.packages.contains_key(*name))
+            .collect::<FxHashSet<_>>();
+        let mut included = BTreeSet::new();
+        let mut excluded = BTreeSet::new();
+
+        for selector in selectors {
+            let mut selected = BTreeSet::new();
+
+            for name in self.match_selector(selector, &changed)? {
+                if selector.include_dependencies {
+                    selected.extend(
+                        self.all_dependencies_of(name, &all_types, None)?
+                            .into_keys(),
+                    );
+                }
+
+                if selector.include_dependents {
+                    selected.extend(self.all_dependents_of(name, &all_types, None)?.into_keys());
+                }
+
+                if selector.includes_self() {
+                    selected.insert(name.to_owned());
+                }
+            }
+
+            // The root package is not a workspace package
+            selected.retain(|name| self.packages.contains_key(name));
+
+            if selector.exclude {
+                excluded.extend(selected);
+            } else {
+                included.extend(selected);
+            }
+        }
+
+        if !selectors.is_empty() && selectors.iter().all(|selector| selector.exclude) {
+            included.extend(self.packages.keys().cloned());
+        }
+
+        Ok(included.difference(&excluded).cloned().collect())
+    }
+
+    fn match_selector(
+        &self,
+        selector: &PackageSelector,
+        changed: &FxHashSet<&str>,
+    ) -> Result<Vec<&str>, PackageGraphError> {
+        let name_glob = match &selector.name {
+            Some(name) if glob::is_glob(name) => Some(glob::GlobSet::new([name])?),
+            _ => None,
+        };
+
+        // Directories are relative to the working directory, while
+        // package roots are matched relative to the workspace root
+        let dir = match &selector.directory {
+            Some(dir) => match self.cwd.join(dir).clean().strip_prefix(&self.root) {
+                Ok(dir) => Some(glob::normalize(dir)?),
+                Err(_) => return Ok(vec![]),
+            },
+            None => None,
+        };
+        let dir_glob = match &dir {
+            Some(dir) if glob::is_glob(dir) => Some(glob::GlobSet::new([dir])?),
+            _ => None,
+        };
+
+        let mut matches = vec![];
+
+        for (name, package) in &self.packages {
+            if let Some(pattern) = &selector.name {
+                let is_match = match &name_glob {
+                    Some(glob) => glob.matches(name),
+                    None => name == pattern,
+                };
+
+                if !is_match {
+                    continue;
+                }
+            }
+
+            if let Some(dir) = &dir {
+                let package_dir = glob::normalize(
+                    package
+                        .root
+                        .strip_prefix(&self.root)
+                        .unwrap_or(&package.root),
+                )?;
+
+                let is_match = match &dir_glob {
+                    Some(glob) => glob.matches(&package_dir),
+                    None => &package_dir == dir,
+                };
+
+                if !is_match {
+                    continue;
+                }
+            }
+
+            if selector.changed_since.is_some() && !changed.contains(name.as_str()) {
+                continue;
+            }
+
+            matches.push(name.as_str());
+        }
+
+        Ok(matches)
+    }
+}
@@ -0,0 +1,105 @@
+use crate::package::Package;
+use crate::package_graph::PackageGraph;
+use crate::package_graph_error::PackageGraphError;
+use clean_path::Clean;
+use nodejs_package_json::{PackageJson, VersionProtocol, WorkspaceProtocol};
+use std::str::FromStr;
+
+impl PackageGraph {
+    // Returns a copy of the package's manifest as it would be published by
+    // pnpm or Yarn: `catalog:` and `workspace:` references are replaced with
+    // concrete versions, and `publishConfig` fields are applied.
+    pub fn create_publish_manifest(&self, name: &str) -> Result<PackageJson, PackageGraphError> {
+        let package = self
+            .packages
+            .get(name)
+            .ok_or_else(|| PackageGraphError::UnknownPackage(name.to_owned()))?;
+
+        let mut manifest = self.resolve_catalog_references(name)?;
+
+        for deps in [
+            &mut manifest.dependencies,
+            &mut manifest.dev_dependencies,
+            &mut manifest.peer_dependencies,
+            &mut manifest.optional_dependencies,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            for (dep_name, version) in deps.iter_mut() {
+                if let VersionProtocol::Workspace(ws) = version {
+                    *version = self.resolve_workspace_reference(package, dep_name, ws)?;
+                }
+            }
+        }
+
+        manifest.apply_publish_config();
+
+        Ok(manifest)
+    }
+
+    fn resolve_workspace_reference(
+        &self,
+        package: &Package,
+        name: &str,
+        protocol: &WorkspaceProtocol,
+    ) -> Result<VersionProtocol, PackageGraphError> {
+        let get_version = |dep_package: Option<&Package>, dep_name: &str| {
+            dep_package
+                .ok_or_else(|| PackageGraphError::UnknownPackage(dep_name.to_owned()))?
+                .manifest
+                .version
+                .clone()
+                .ok_or_else(|| PackageGraphError::MissingPackageVersion(dep_name.to_owned()))
+        };
+
+        let (alias, requirement) = match protocol {
+            // workspace:* -> 1.2.3
+            WorkspaceProtocol::Any { alias } => {
+                let dep_name = alias.as_deref().unwrap_or(name);
+
+                (
+                    alias,
+                    get_version(self.packages.get(dep_name), dep_name)?.to_string(),
+                )
+            }
+            // workspace:~ -> ~1.2.3, workspace:^ -> ^1.2.3
+            WorkspaceProtocol::Tilde { alias, version }
+            | WorkspaceProtocol::Caret { alias, version } => {
+                let dep_name = alias.as_deref().unwrap_or(name);
+                let version = match version {
+                    Some(version) => version.to_owned(),
+                    None => get_version(self.packages.get(dep_name), dep_name)?,
+                };
+                let prefix = if matches!(protocol, WorkspaceProtocol::Tilde { .. }) {
+                    '~'
+                } else {
+                    '^'
+                };
+
+                (alias, format!("{prefix}{version}"))
+            }
+            // workspace:../path -> 1.2.3
+            WorkspaceProtocol::File(path) => {
+                let root = package.root.join(path).clean();
+
+                (
+                    &None,
+                    get_version(
+                        self.packages.values().find(|dep| dep.root == root),
+                        &path.to_string_lossy(),
+                    )?
+                    .to_string(),
+                )
+            }
+            // workspace:1.2.3 -> 1.2.3
+            WorkspaceProtocol::Version(version) => (&None, version.to_string()),
+        };
+
+        // workspace:package@* -> npm:package@1.2.3
+        Ok(match alias {
+            Some(alias) => VersionProtocol::from_str(&format!("npm:{alias}@{requirement}"))?,
+            None => VersionProtocol::from_str(&requirement)?,
+        })
+    }
+}
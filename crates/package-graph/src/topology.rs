@@ -0,0 +1,72 @@
+use crate::package::DependencyType;
+use crate::package_graph::{PackageGraph, PackageGraphType};
+use petgraph::algo::tarjan_scc;
+use petgraph::Direction;
+use rustc_hash::FxHashMap;
+
+impl PackageGraph {
+    // Returns workspace packages in topological order, where a package is
+    // always ordered after its dependencies. Only edges of the provided
+    // types are followed. Packages in a cycle are ordered by name.
+    pub fn sort_topological(&self, types: &[DependencyType]) -> Vec<String> {
+        self.sort_batched_topological(types)
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    // Returns workspace packages grouped into batches, where each batch only
+    // depends on packages in previous batches, and can be processed in parallel.
+    // Only edges of the provided types are followed. Packages in a cycle are
+    // placed in the same batch.
+    pub fn sort_batched_topological(&self, types: &[DependencyType]) -> Vec<Vec<String>> {
+        let graph = self.to_filtered_graph(types);
+
+        // Components are returned in reverse topological order,
+        // so dependencies are always visited before dependents
+        let components = tarjan_scc(&graph);
+        let mut component_of = FxHashMap::default();
+        let mut levels = vec![0; components.len()];
+        let mut batches: Vec<Vec<String>> = vec![];
+
+        for (index, component) in components.iter().enumerate() {
+            for node in component {
+                component_of.insert(*node, index);
+            }
+
+            let level = component
+                .iter()
+                .flat_map(|node| graph.neighbors_directed(*node, Direction::Outgoing))
+                .filter_map(|dep| component_of.get(&dep))
+                .filter(|dep_index| **dep_index != index)
+                .map(|dep_index| levels[*dep_index] + 1)
+                .max()
+                .unwrap_or(0);
+
+            levels[index] = level;
+
+            if batches.len() <= level {
+                batches.resize_with(level + 1, Vec::new);
+            }
+
+            batches[level].extend(component.iter().map(|node| graph[*node].to_owned()));
+        }
+
+        for batch in &mut batches {
+            batch.sort();
+        }
+
+        batches
+    }
+
+    // Returns a graph of workspace packages, without the root package,
+    // and only with edges of the provided types.
+    pub(crate) fn to_filtered_graph(&self, types: &[DependencyType]) -> PackageGraphType {
+        let root = self.root_package.node_index;
+
+        self.graph.filter_map(
+            |index, name| (index != root).then(|| name.to_owned()),
+            |_, dep_type| types.contains(dep_type).then_some(*dep_type),
+        )
+    }
+}
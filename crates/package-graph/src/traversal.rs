@@ -0,0 +1,134 @@
+use crate::package::DependencyType;
+use crate::package_graph::PackageGraph;
+use crate::package_graph_error::PackageGraphError;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use rustc_hash::FxHashMap;
+use std::collections::{BTreeMap, VecDeque};
+
+impl PackageGraph {
+    // Returns all direct and transitive dependencies of a package, mapped to
+    // the shortest distance from the package. Only edges of the provided
+    // types are followed, up to the maximum depth if provided.
+    pub fn all_dependencies_of(
+        &self,
+        name: &str,
+        types: &[DependencyType],
+        max_depth: Option<usize>,
+    ) -> Result<BTreeMap<String, usize>, PackageGraphError> {
+        self.traverse(name, Direction::Outgoing, types, max_depth)
+    }
+
+    // Returns all direct and transitive dependents of a package, mapped to
+    // the shortest distance from the package. Only edges of the provided
+    // types are followed, up to the maximum depth if provided. The root
+    // package is not a workspace package, and is never returned.
+    pub fn all_dependents_of(
+        &self,
+        name: &str,
+        types: &[DependencyType],
+        max_depth: Option<usize>,
+    ) -> Result<BTreeMap<String, usize>, PackageGraphError> {
+        self.traverse(name, Direction::Incoming, types, max_depth)
+    }
+
+    // Returns the shortest chain of dependencies from one package to another,
+    // explaining why the former depends on the latter. Each step is the next
+    // package in the chain, and the type of dependency used to reach it.
+    pub fn find_dependency_path(
+        &self,
+        from: &str,
+        to: &str,
+        types: &[DependencyType],
+    ) -> Result<Option<Vec<(String, DependencyType)>>, PackageGraphError> {
+        let start = self.get_node_index(from)?;
+        let end = self.get_node_index(to)?;
+        let mut parents = FxHashMap::default();
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(node) = queue.pop_front() {
+            if node == end {
+                break;
+            }
+
+            for edge in self.graph.edges_directed(node, Direction::Outgoing) {
+                let target = edge.target();
+
+                if target == start
+                    || parents.contains_key(&target)
+                    || !types.contains(edge.weight())
+                {
+                    continue;
+                }
+
+                parents.insert(target, (node, *edge.weight()));
+                queue.push_back(target);
+            }
+        }
+
+        if start != end && !parents.contains_key(&end) {
+            return Ok(None);
+        }
+
+        // Walk backwards from the end to rebuild the path
+        let mut path = vec![];
+        let mut current = end;
+
+        while let Some((parent, dep_type)) = parents.get(&current) {
+            path.push((self.graph[current].to_owned(), *dep_type));
+            current = *parent;
+        }
+
+        path.reverse();
+
+        Ok(Some(path))
+    }
+
+    fn traverse(
+        &self,
+        name: &str,
+        direction: Direction,
+        types: &[DependencyType],
+        max_depth: Option<usize>,
+    ) -> Result<BTreeMap<String, usize>, PackageGraphError> {
+        let start = self.get_node_index(name)?;
+        let mut depths = FxHashMap::from_iter([(start, 0)]);
+        let mut queue = VecDeque::from([start]);
+
+        while let Some(node) = queue.pop_front() {
+            let depth = depths[&node] + 1;
+
+            if max_depth.is_some_and(|max| depth > max) {
+                continue;
+            }
+
+            for edge in self.graph.edges_directed(node, direction) {
+                let next = match direction {
+                    Direction::Outgoing => edge.target(),
+                    Direction::Incoming => edge.source(),
+                };
+
+                if depths.contains_key(&next) || !types.contains(edge.weight()) {
+                    continue;
+                }
+
+                depths.insert(next, depth);
+                queue.push_back(next);
+            }
+        }
+
+        Ok(depths
+            .into_iter()
+            .filter(|(node, _)| *node != start && *node != self.root_package.node_index)
+            .map(|(node, depth)| (self.graph[node].to_owned(), depth))
+            .collect())
+    }
+
+    fn get_node_index(&self, name: &str) -> Result<NodeIndex, PackageGraphError> {
+        self.packages
+            .get(name)
+            .map(|package| package.node_index)
+            .ok_or_else(|| PackageGraphError::UnknownPackage(name.to_owned()))
+    }
+}
@@ -1,5 +1,6 @@
 use nodejs_package_graph::*;
-use starbase_sandbox::{assert_snapshot, create_sandbox};
+use nodejs_package_managers::PackageManager;
+use starbase_sandbox::{assert_snapshot, create_empty_sandbox, create_sandbox};
 
 mod polyrepo {
     use super::*;
@@ -13,6 +14,65 @@ mod polyrepo {
     }
 }
 
+mod package_manager {
+    use super::*;
+
+    #[test]
+    fn detects_from_lockfile() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("package.json", r#"{ "name": "root" }"#);
+        sandbox.create_file("yarn.lock", "");
+        sandbox.create_file(".yarnrc.yml", "");
+
+        let graph = PackageGraph::load_from(sandbox.path()).unwrap();
+
+        assert_eq!(graph.manager, PackageManager::Yarn);
+    }
+
+    #[test]
+    fn prefers_package_manager_field() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(
+            "package.json",
+            r#"{ "name": "root", "packageManager": "yarn@1.22.19+sha1.4ba7fc5c6e704fce2066ecbfb0b0d8976fe62447" }"#,
+        );
+        sandbox.create_file("yarn.lock", "");
+        sandbox.create_file(".yarnrc.yml", "");
+
+        let graph = PackageGraph::load_from(sandbox.path()).unwrap();
+
+        assert_eq!(graph.manager, PackageManager::YarnLegacy);
+    }
+
+    #[test]
+    fn prefers_dev_engines_field() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(
+            "package.json",
+            r#"{ "name": "root", "devEngines": { "packageManager": { "name": "yarn", "version": "^4.1.0" } } }"#,
+        );
+        sandbox.create_file("yarn.lock", "");
+
+        let graph = PackageGraph::load_from(sandbox.path()).unwrap();
+
+        assert_eq!(graph.manager, PackageManager::Yarn);
+    }
+
+    #[test]
+    fn ignores_yarn_dev_engine_without_version() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(
+            "package.json",
+            r#"{ "name": "root", "devEngines": { "packageManager": [{ "name": "yarn" }, { "name": "npm" }] } }"#,
+        );
+        sandbox.create_file("yarn.lock", "");
+
+        let graph = PackageGraph::load_from(sandbox.path()).unwrap();
+
+        assert_eq!(graph.manager, PackageManager::YarnLegacy);
+    }
+}
+
 mod monorepo {
     use super::*;
 
@@ -55,6 +115,31 @@ mod monorepo {
             );
         }
 
+        #[test]
+        fn links_version_range_if_match() {
+            let sandbox = create_sandbox("graph-protocols");
+            let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+            assert_eq!(
+                graph.dependencies_of("ver-range").unwrap(),
+                vec![
+                    ("b".into(), DependencyType::Development),
+                    ("a".into(), DependencyType::Production),
+                ]
+            );
+        }
+
+        #[test]
+        fn links_alias() {
+            let sandbox = create_sandbox("graph-protocols");
+            let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+            assert_eq!(
+                graph.dependencies_of("alias").unwrap(),
+                vec![("a".into(), DependencyType::Production)]
+            );
+        }
+
         #[test]
         fn links_file() {
             let sandbox = create_sandbox("graph-protocols");
@@ -206,6 +291,97 @@ mod monorepo {
     }
 }
 
+mod engines {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn intersects_all_packages() {
+        let sandbox = create_sandbox("graph-engines");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let result = graph.intersect_engines("node");
+
+        assert_eq!(
+            result.range.unwrap().to_string(),
+            ">=20.9.0 <21.0.0-0 || >=22.0.0"
+        );
+        assert_eq!(
+            result.conflicts,
+            vec![
+                ("a".into(), "c".into()),
+                ("b".into(), "c".into()),
+                ("c".into(), "root".into()),
+            ]
+        );
+        assert_eq!(result.excluded, vec!["c"]);
+    }
+
+    #[test]
+    fn reports_every_package_of_a_conflict() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(
+            "package.json",
+            r#"{ "name": "root", "workspaces": ["packages/*"], "engines": { "node": ">=20" } }"#,
+        );
+        sandbox.create_file(
+            "packages/a/package.json",
+            r#"{ "name": "a", "engines": { "node": "^18" } }"#,
+        );
+        sandbox.create_file(
+            "packages/b/package.json",
+            r#"{ "name": "b", "engines": { "node": "^18.5" } }"#,
+        );
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let result = graph.intersect_engines("node");
+
+        assert_eq!(result.range.unwrap().to_string(), ">=18.5.0 <19.0.0-0");
+        assert_eq!(
+            result.conflicts,
+            vec![("a".into(), "root".into()), ("b".into(), "root".into()),]
+        );
+        assert_eq!(result.excluded, vec!["root"]);
+    }
+
+    #[test]
+    fn reports_unparseable_ranges() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(
+            "package.json",
+            r#"{ "name": "root", "workspaces": ["packages/*"], "engines": { "node": ">=20" } }"#,
+        );
+        sandbox.create_file(
+            "packages/a/package.json",
+            r#"{ "name": "a", "engines": { "node": "latest" } }"#,
+        );
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let result = graph.intersect_engines("node");
+
+        assert_eq!(result.range.unwrap().to_string(), ">=20");
+        assert!(result.conflicts.is_empty());
+        assert_eq!(
+            result.unparseable,
+            BTreeMap::from_iter([("a".into(), "latest".into())])
+        );
+    }
+
+    #[test]
+    fn returns_none_if_not_defined() {
+        let sandbox = create_sandbox("graph-engines");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.intersect_engines("npm").range.unwrap().to_string(),
+            ">=10"
+        );
+        assert_eq!(
+            graph.intersect_engines("bun"),
+            EngineIntersection::default()
+        );
+    }
+}
+
 mod monorepo_npm {
     use super::*;
 
@@ -230,6 +406,1102 @@ mod monorepo_pnpm {
     }
 }
 
+mod topology {
+    use super::*;
+    use nodejs_package_json::DependencyType;
+
+    const ALL_TYPES: [DependencyType; 4] = [
+        DependencyType::Production,
+        DependencyType::Development,
+        DependencyType::Peer,
+        DependencyType::Optional,
+    ];
+
+    #[test]
+    fn batches_all_types() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.sort_batched_topological(&ALL_TYPES),
+            vec![vec!["a", "f"], vec!["b"], vec!["c"], vec!["d"], vec!["e"],]
+        );
+    }
+
+    #[test]
+    fn batches_filtered_types() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.sort_batched_topological(&[DependencyType::Production]),
+            vec![vec!["a", "e", "f"], vec!["b", "c"], vec!["d"]]
+        );
+    }
+
+    #[test]
+    fn sorts_in_order() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.sort_topological(&ALL_TYPES),
+            vec!["a", "f", "b", "c", "d", "e"]
+        );
+        assert_eq!(
+            graph.sort_topological(&[DependencyType::Production, DependencyType::Peer]),
+            vec!["a", "f", "b", "c", "d", "e"]
+        );
+    }
+
+    #[test]
+    fn batches_cycles_together() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(
+            "package.json",
+            r#"{ "name": "root", "workspaces": ["packages/*"] }"#,
+        );
+        sandbox.create_file(
+            "packages/a/package.json",
+            r#"{ "name": "a", "version": "1.0.0", "dependencies": { "b": "*" } }"#,
+        );
+        sandbox.create_file(
+            "packages/b/package.json",
+            r#"{ "name": "b", "version": "1.0.0", "dependencies": { "a": "*" } }"#,
+        );
+        sandbox.create_file(
+            "packages/c/package.json",
+            r#"{ "name": "c", "version": "1.0.0", "dependencies": { "a": "*" } }"#,
+        );
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.sort_batched_topological(&ALL_TYPES),
+            vec![vec!["a", "b"], vec!["c"]]
+        );
+    }
+}
+
+mod relationships {
+    use super::*;
+    use nodejs_package_json::DependencyType;
+    use std::collections::BTreeMap;
+
+    const ALL_TYPES: [DependencyType; 4] = [
+        DependencyType::Production,
+        DependencyType::Development,
+        DependencyType::Peer,
+        DependencyType::Optional,
+    ];
+
+    fn depths(list: &[(&str, usize)]) -> BTreeMap<String, usize> {
+        list.iter()
+            .map(|(name, depth)| (name.to_string(), *depth))
+            .collect()
+    }
+
+    #[test]
+    fn returns_direct_dependents() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let mut dependents = graph.dependents_of("b").unwrap();
+        dependents.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(
+            dependents,
+            vec![
+                ("c".into(), DependencyType::Development),
+                ("d".into(), DependencyType::Optional)
+            ]
+        );
+        assert_eq!(graph.dependents_of("f").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn returns_all_dependencies() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.all_dependencies_of("e", &ALL_TYPES, None).unwrap(),
+            depths(&[("a", 3), ("b", 2), ("c", 2), ("d", 1)])
+        );
+        assert_eq!(
+            graph.all_dependencies_of("a", &ALL_TYPES, None).unwrap(),
+            depths(&[])
+        );
+    }
+
+    #[test]
+    fn returns_all_dependencies_with_max_depth() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.all_dependencies_of("e", &ALL_TYPES, Some(1)).unwrap(),
+            depths(&[("d", 1)])
+        );
+        assert_eq!(
+            graph.all_dependencies_of("e", &ALL_TYPES, Some(2)).unwrap(),
+            depths(&[("b", 2), ("c", 2), ("d", 1)])
+        );
+    }
+
+    #[test]
+    fn returns_all_dependencies_with_types() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph
+                .all_dependencies_of("e", &[DependencyType::Production], None)
+                .unwrap(),
+            depths(&[])
+        );
+        assert_eq!(
+            graph
+                .all_dependencies_of("d", &[DependencyType::Production], None)
+                .unwrap(),
+            depths(&[("a", 2), ("c", 1)])
+        );
+    }
+
+    #[test]
+    fn returns_all_dependents() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.all_dependents_of("a", &ALL_TYPES, None).unwrap(),
+            depths(&[("b", 1), ("c", 1), ("d", 2), ("e", 3)])
+        );
+        assert_eq!(
+            graph.all_dependents_of("a", &ALL_TYPES, Some(1)).unwrap(),
+            depths(&[("b", 1), ("c", 1)])
+        );
+        assert_eq!(
+            graph
+                .all_dependents_of("b", &[DependencyType::Production], None)
+                .unwrap(),
+            depths(&[])
+        );
+        assert_eq!(
+            graph.all_dependents_of("f", &ALL_TYPES, None).unwrap(),
+            depths(&[])
+        );
+    }
+
+    #[test]
+    fn errors_for_unknown_package() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert!(matches!(
+            graph.all_dependencies_of("unknown", &ALL_TYPES, None),
+            Err(PackageGraphError::UnknownPackage(_))
+        ));
+        assert!(matches!(
+            graph.find_dependency_path("a", "unknown", &ALL_TYPES),
+            Err(PackageGraphError::UnknownPackage(_))
+        ));
+    }
+
+    #[test]
+    fn finds_dependency_path() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.find_dependency_path("e", "c", &ALL_TYPES).unwrap(),
+            Some(vec![
+                ("d".into(), DependencyType::Peer),
+                ("c".into(), DependencyType::Production)
+            ])
+        );
+        assert_eq!(
+            graph
+                .find_dependency_path("d", "a", &[DependencyType::Production])
+                .unwrap(),
+            Some(vec![
+                ("c".into(), DependencyType::Production),
+                ("a".into(), DependencyType::Production)
+            ])
+        );
+        assert_eq!(
+            graph.find_dependency_path("a", "a", &ALL_TYPES).unwrap(),
+            Some(vec![])
+        );
+    }
+
+    #[test]
+    fn returns_no_path_if_unrelated() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.find_dependency_path("a", "e", &ALL_TYPES).unwrap(),
+            None
+        );
+        assert_eq!(
+            graph
+                .find_dependency_path("c", "b", &[DependencyType::Production])
+                .unwrap(),
+            None
+        );
+    }
+}
+
+mod affected {
+    use super::*;
+    use nodejs_package_json::DependencyType;
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn changed(file: &str) -> AffectedReason {
+        AffectedReason::ChangedFile(PathBuf::from(file))
+    }
+
+    fn dependency(name: &str, dep_type: DependencyType) -> AffectedReason {
+        AffectedReason::Dependency {
+            name: name.into(),
+            dep_type,
+        }
+    }
+
+    fn names(affected: &BTreeMap<String, Vec<AffectedReason>>) -> Vec<&str> {
+        affected.keys().map(|name| name.as_str()).collect()
+    }
+
+    #[test]
+    fn finds_owning_package() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        let get_name = |file: PathBuf| {
+            graph
+                .get_owning_package(file)
+                .and_then(|package| package.manifest.name.clone())
+        };
+
+        assert_eq!(
+            get_name(PathBuf::from("packages/a/src/index.js")),
+            Some("a".into())
+        );
+        assert_eq!(
+            get_name(sandbox.path().join("packages/b/package.json")),
+            Some("b".into())
+        );
+        assert_eq!(get_name(PathBuf::from("README.md")), Some("root".into()));
+        assert_eq!(
+            get_name(PathBuf::from("packages/unknown/index.js")),
+            Some("root".into())
+        );
+        assert_eq!(get_name(PathBuf::from("../outside.js")), None);
+    }
+
+    #[test]
+    fn finds_deepest_owning_package() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(
+            "package.json",
+            r#"{ "name": "root", "workspaces": ["packages/a", "packages/a/nested"] }"#,
+        );
+        sandbox.create_file("packages/a/package.json", r#"{ "name": "a" }"#);
+        sandbox.create_file("packages/a/nested/package.json", r#"{ "name": "nested" }"#);
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph
+                .get_owning_package("packages/a/nested/src/index.js")
+                .unwrap()
+                .manifest
+                .name,
+            Some("nested".into())
+        );
+        assert_eq!(
+            graph
+                .get_owning_package("packages/a/src/index.js")
+                .unwrap()
+                .manifest
+                .name,
+            Some("a".into())
+        );
+    }
+
+    #[test]
+    fn includes_changed_packages() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let affected = graph.get_affected_packages(
+            [
+                "packages/e/index.js",
+                "packages/e/package.json",
+                "README.md",
+            ],
+            &AffectedRules::default(),
+        );
+
+        assert_eq!(
+            affected,
+            BTreeMap::from_iter([
+                (
+                    "e".into(),
+                    vec![
+                        changed("packages/e/index.js"),
+                        changed("packages/e/package.json")
+                    ]
+                ),
+                ("root".into(), vec![changed("README.md")]),
+            ])
+        );
+    }
+
+    #[test]
+    fn expands_to_transitive_dependents() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let affected =
+            graph.get_affected_packages(["packages/a/index.js"], &AffectedRules::default());
+
+        assert_eq!(names(&affected), vec!["a", "b", "c", "d", "e"]);
+        assert_eq!(affected["a"], vec![changed("packages/a/index.js")]);
+        assert_eq!(
+            affected["b"],
+            vec![dependency("a", DependencyType::Production)]
+        );
+        assert!(affected["c"].contains(&dependency("a", DependencyType::Production)));
+        assert!(affected["c"].contains(&dependency("b", DependencyType::Development)));
+        assert_eq!(affected["e"], vec![dependency("d", DependencyType::Peer)]);
+    }
+
+    #[test]
+    fn doesnt_expand_past_direct_dependents() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let rules = AffectedRules {
+            optional: AffectedPropagation::None,
+            ..AffectedRules::default()
+        };
+
+        // c is affected through a dev dependency, so d is not
+        let affected = graph.get_affected_packages(["packages/b/index.js"], &rules);
+
+        assert_eq!(names(&affected), vec!["b", "c"]);
+        assert_eq!(
+            affected["c"],
+            vec![dependency("b", DependencyType::Development)]
+        );
+
+        // f is only a dev dependency of the root
+        let affected = graph.get_affected_packages(["packages/f/index.js"], &rules);
+
+        assert_eq!(names(&affected), vec!["f", "root"]);
+    }
+
+    #[test]
+    fn follows_custom_rules() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let rules = AffectedRules {
+            development: AffectedPropagation::Transitive,
+            optional: AffectedPropagation::Transitive,
+            peer: AffectedPropagation::None,
+            production: AffectedPropagation::Direct,
+        };
+
+        let affected = graph.get_affected_packages(["packages/a/index.js"], &rules);
+
+        assert_eq!(names(&affected), vec!["a", "b", "c"]);
+
+        let affected = graph.get_affected_packages(["packages/b/index.js"], &rules);
+
+        assert_eq!(names(&affected), vec!["b", "c", "d"]);
+    }
+
+    #[test]
+    fn ignores_files_outside_workspace() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert!(graph
+            .get_affected_packages(["../other/index.js"], &AffectedRules::default())
+            .is_empty());
+    }
+}
+
+mod selectors {
+    use super::*;
+    use std::str::FromStr;
+
+    fn filter(graph: &PackageGraph, selectors: &[&str], changed_files: &[&str]) -> Vec<String> {
+        let selectors = selectors
+            .iter()
+            .map(|selector| PackageSelector::from_str(selector).unwrap())
+            .collect::<Vec<_>>();
+
+        graph.filter_packages(&selectors, changed_files).unwrap()
+    }
+
+    #[test]
+    fn parses_names() {
+        assert_eq!(
+            PackageSelector::from_str("foo").unwrap(),
+            PackageSelector {
+                name: Some("foo".into()),
+                ..PackageSelector::default()
+            }
+        );
+        assert_eq!(
+            PackageSelector::from_str("!@scope/*").unwrap(),
+            PackageSelector {
+                exclude: true,
+                name: Some("@scope/*".into()),
+                ..PackageSelector::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parses_relationships() {
+        assert_eq!(
+            PackageSelector::from_str("...foo...").unwrap(),
+            PackageSelector {
+                include_dependencies: true,
+                include_dependents: true,
+                name: Some("foo".into()),
+                ..PackageSelector::default()
+            }
+        );
+        assert_eq!(
+            PackageSelector::from_str("foo^...").unwrap(),
+            PackageSelector {
+                exclude_self_from_dependencies: true,
+                include_dependencies: true,
+                name: Some("foo".into()),
+                ..PackageSelector::default()
+            }
+        );
+
+        for value in ["...^foo", "^...foo"] {
+            assert_eq!(
+                PackageSelector::from_str(value).unwrap(),
+                PackageSelector {
+                    exclude_self_from_dependents: true,
+                    include_dependents: true,
+                    name: Some("foo".into()),
+                    ..PackageSelector::default()
+                }
+            );
+        }
+
+        assert_eq!(
+            PackageSelector::from_str("...^foo...").unwrap(),
+            PackageSelector {
+                exclude_self_from_dependents: true,
+                include_dependencies: true,
+                include_dependents: true,
+                name: Some("foo".into()),
+                ..PackageSelector::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parses_directories_and_changes() {
+        assert_eq!(
+            PackageSelector::from_str("./packages/**").unwrap(),
+            PackageSelector {
+                directory: Some("./packages/**".into()),
+                ..PackageSelector::default()
+            }
+        );
+        assert_eq!(
+            PackageSelector::from_str("...{packages/foo}[origin/main]").unwrap(),
+            PackageSelector {
+                changed_since: Some("origin/main".into()),
+                directory: Some("packages/foo".into()),
+                include_dependents: true,
+                ..PackageSelector::default()
+            }
+        );
+    }
+
+    #[test]
+    fn errors_for_invalid_selectors() {
+        for value in ["", "!", "...", "foo[]", "foo]", "{packages/foo"] {
+            assert!(matches!(
+                PackageSelector::from_str(value),
+                Err(PackageGraphError::InvalidSelector(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn selects_by_name() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(filter(&graph, &["a"], &[]), vec!["a"]);
+        assert_eq!(filter(&graph, &["a", "c"], &[]), vec!["a", "c"]);
+        assert_eq!(filter(&graph, &["unknown"], &[]), Vec::<String>::new());
+    }
+
+    #[test]
+    fn selects_by_name_glob() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(
+            "package.json",
+            r#"{ "name": "root", "workspaces": ["packages/*"] }"#,
+        );
+        sandbox.create_file("packages/a/package.json", r#"{ "name": "@scope/a" }"#);
+        sandbox.create_file("packages/b/package.json", r#"{ "name": "@scope/b" }"#);
+        sandbox.create_file("packages/c/package.json", r#"{ "name": "@other/c" }"#);
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            filter(&graph, &["@scope/*"], &[]),
+            vec!["@scope/a", "@scope/b"]
+        );
+        assert_eq!(filter(&graph, &["!@scope/*"], &[]), vec!["@other/c"]);
+    }
+
+    #[test]
+    fn selects_dependencies() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(filter(&graph, &["d..."], &[]), vec!["a", "b", "c", "d"]);
+        assert_eq!(filter(&graph, &["d^..."], &[]), vec!["a", "b", "c"]);
+        assert_eq!(filter(&graph, &["a..."], &[]), vec!["a"]);
+    }
+
+    #[test]
+    fn selects_dependents() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(filter(&graph, &["...b"], &[]), vec!["b", "c", "d", "e"]);
+        assert_eq!(filter(&graph, &["...^b"], &[]), vec!["c", "d", "e"]);
+        assert_eq!(filter(&graph, &["^...b"], &[]), vec!["c", "d", "e"]);
+
+        // The root package is never selected
+        assert_eq!(filter(&graph, &["...f"], &[]), vec!["f"]);
+    }
+
+    #[test]
+    fn excludes_self_per_relationship() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            filter(&graph, &["...^c..."], &[]),
+            vec!["a", "b", "c", "d", "e"]
+        );
+        assert_eq!(
+            filter(&graph, &["...c^..."], &[]),
+            vec!["a", "b", "c", "d", "e"]
+        );
+        assert_eq!(
+            filter(&graph, &["...^c^..."], &[]),
+            vec!["a", "b", "d", "e"]
+        );
+    }
+
+    #[test]
+    fn selects_nothing_without_selectors() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(filter(&graph, &[], &[]), Vec::<String>::new());
+    }
+
+    #[test]
+    fn selects_by_directory() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            filter(&graph, &["./packages/**"], &[]),
+            vec!["a", "b", "c", "d", "e", "f"]
+        );
+        assert_eq!(filter(&graph, &["./packages/b"], &[]), vec!["b"]);
+        assert_eq!(filter(&graph, &["{packages/b}..."], &[]), vec!["a", "b"]);
+        assert_eq!(filter(&graph, &["./other/**"], &[]), Vec::<String>::new());
+    }
+
+    #[test]
+    fn excludes_packages() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(filter(&graph, &["!b"], &[]), vec!["a", "c", "d", "e", "f"]);
+        assert_eq!(filter(&graph, &["!b", "!...^c"], &[]), vec!["a", "c", "f"]);
+        assert_eq!(
+            filter(&graph, &["...a", "!e"], &[]),
+            vec!["a", "b", "c", "d"]
+        );
+    }
+
+    #[test]
+    fn selects_changed_packages() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let changed = ["packages/c/src/index.js", "README.md"];
+
+        assert_eq!(filter(&graph, &["[origin/main]"], &changed), vec!["c"]);
+        assert_eq!(
+            filter(&graph, &["...[origin/main]"], &changed),
+            vec!["c", "d", "e"]
+        );
+        assert_eq!(
+            filter(&graph, &["[origin/main]^..."], &changed),
+            vec!["a", "b"]
+        );
+        assert_eq!(
+            filter(&graph, &["{packages/f}[origin/main]"], &changed),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            graph
+                .filter_packages(
+                    &[PackageSelector::from_str("[HEAD~1]").unwrap()],
+                    &[sandbox.path().join("packages/a/index.js")]
+                )
+                .unwrap(),
+            vec!["a"]
+        );
+        assert_eq!(
+            filter(&graph, &["[origin/main]"], &[]),
+            Vec::<String>::new()
+        );
+    }
+}
+
+mod cycles {
+    use super::*;
+    use nodejs_package_json::DependencyType;
+    use starbase_sandbox::Sandbox;
+
+    fn create_package(sandbox: &Sandbox, name: &str, deps: &str) {
+        sandbox.create_file(
+            format!("packages/{name}/package.json"),
+            format!(r#"{{ "name": "{name}", "version": "1.0.0", {deps} }}"#),
+        );
+    }
+
+    fn create_workspace() -> Sandbox {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file(
+            "package.json",
+            r#"{ "name": "root", "workspaces": ["packages/*"] }"#,
+        );
+        sandbox
+    }
+
+    #[test]
+    fn returns_nothing_without_cycles() {
+        let sandbox = create_sandbox("graph-topology");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert!(graph.detect_cycles().is_empty());
+        assert!(graph.validate_cycles(false).unwrap().is_empty());
+    }
+
+    #[test]
+    fn detects_production_cycles() {
+        let sandbox = create_workspace();
+        create_package(&sandbox, "a", r#""dependencies": { "b": "*" }"#);
+        create_package(&sandbox, "b", r#""optionalDependencies": { "c": "*" }"#);
+        create_package(&sandbox, "c", r#""dependencies": { "a": "*" }"#);
+        create_package(&sandbox, "d", r#""dependencies": { "a": "*" }"#);
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.detect_cycles(),
+            vec![DependencyCycle {
+                packages: vec!["a".into(), "b".into(), "c".into()],
+                edges: vec![
+                    DependencyType::Production,
+                    DependencyType::Optional,
+                    DependencyType::Production
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn detects_self_cycles() {
+        let sandbox = create_workspace();
+        create_package(&sandbox, "a", r#""devDependencies": { "a": "*" }"#);
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.detect_cycles(),
+            vec![DependencyCycle {
+                packages: vec!["a".into()],
+                edges: vec![DependencyType::Development],
+            }]
+        );
+    }
+
+    #[test]
+    fn detects_multiple_cycles() {
+        let sandbox = create_workspace();
+        create_package(&sandbox, "a", r#""dependencies": { "b": "*" }"#);
+        create_package(&sandbox, "b", r#""dependencies": { "a": "*" }"#);
+        create_package(&sandbox, "c", r#""devDependencies": { "d": "*" }"#);
+        create_package(&sandbox, "d", r#""peerDependencies": { "c": "*" }"#);
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let cycles = graph.detect_cycles();
+
+        assert_eq!(cycles.len(), 2);
+        assert_eq!(cycles[0].packages, vec!["a", "b"]);
+        assert!(!cycles[0].is_dev_only());
+        assert_eq!(cycles[1].packages, vec!["c", "d"]);
+        assert!(cycles[1].is_dev_only());
+    }
+
+    #[test]
+    fn prefers_production_cycles_in_mixed_components() {
+        let sandbox = create_workspace();
+        create_package(
+            &sandbox,
+            "a",
+            r#""dependencies": { "b": "*" }, "devDependencies": { "c": "*" }"#,
+        );
+        create_package(&sandbox, "b", r#""dependencies": { "c": "*" }"#);
+        create_package(&sandbox, "c", r#""dependencies": { "b": "*" }"#);
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let cycles = graph.detect_cycles();
+
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(cycles[0].packages, vec!["b", "c"]);
+        assert!(!cycles[0].is_dev_only());
+    }
+
+    #[test]
+    fn detects_every_cycle_in_a_component() {
+        let sandbox = create_workspace();
+        create_package(
+            &sandbox,
+            "a",
+            r#""dependencies": { "b": "*" }, "devDependencies": { "c": "*" }"#,
+        );
+        create_package(&sandbox, "b", r#""devDependencies": { "a": "*" }"#);
+        create_package(&sandbox, "c", r#""devDependencies": { "a": "*" }"#);
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.detect_cycles(),
+            vec![
+                DependencyCycle {
+                    packages: vec!["a".into(), "b".into()],
+                    edges: vec![DependencyType::Production, DependencyType::Development],
+                },
+                DependencyCycle {
+                    packages: vec!["a".into(), "c".into()],
+                    edges: vec![DependencyType::Development, DependencyType::Development],
+                }
+            ]
+        );
+
+        assert_eq!(
+            graph.validate_cycles(true).unwrap_err().to_string(),
+            "Dependency cycle detected: a --dependencies--> b --devDependencies--> a"
+        );
+    }
+
+    #[test]
+    fn reports_strongest_edge_between_packages() {
+        let sandbox = create_workspace();
+        create_package(
+            &sandbox,
+            "a",
+            r#""devDependencies": { "b": "*" }, "dependencies": { "b": "*" }"#,
+        );
+        create_package(&sandbox, "b", r#""peerDependencies": { "a": "*" }"#);
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let cycles = graph.detect_cycles();
+
+        assert_eq!(cycles.len(), 1);
+        assert_eq!(
+            cycles[0].edges,
+            vec![DependencyType::Production, DependencyType::Peer]
+        );
+        assert!(graph.validate_cycles(true).is_err());
+    }
+
+    #[test]
+    fn returns_dev_cycles_as_warnings() {
+        let sandbox = create_workspace();
+        create_package(&sandbox, "a", r#""devDependencies": { "b": "*" }"#);
+        create_package(&sandbox, "b", r#""peerDependencies": { "a": "*" }"#);
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let warnings = graph.validate_cycles(true).unwrap();
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].packages, vec!["a", "b"]);
+    }
+
+    #[test]
+    fn errors_for_dev_cycles_when_not_warnings() {
+        let sandbox = create_workspace();
+        create_package(&sandbox, "a", r#""devDependencies": { "b": "*" }"#);
+        create_package(&sandbox, "b", r#""peerDependencies": { "a": "*" }"#);
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert!(matches!(
+            graph.validate_cycles(false),
+            Err(PackageGraphError::DependencyCycle(_))
+        ));
+    }
+
+    #[test]
+    fn errors_with_cycle_path() {
+        let sandbox = create_workspace();
+        create_package(&sandbox, "a", r#""dependencies": { "b": "*" }"#);
+        create_package(&sandbox, "b", r#""devDependencies": { "a": "*" }"#);
+
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let error = graph.validate_cycles(true).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "Dependency cycle detected: a --dependencies--> b --devDependencies--> a"
+        );
+    }
+}
+
+mod monorepo_pnpm_catalogs {
+    use super::*;
+    use nodejs_package_json::{Range, VersionProtocol};
+
+    #[test]
+    fn loads_catalogs() {
+        let sandbox = create_sandbox("graph-mono-pnpm-catalogs");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.catalogs.keys().collect::<Vec<_>>(),
+            vec!["default", "legacy"]
+        );
+        assert_eq!(
+            graph.get_catalog_version(None, "react").unwrap(),
+            &VersionProtocol::Requirement(Range::parse("^18.2.0").unwrap())
+        );
+        assert_eq!(
+            graph.get_catalog_version(Some("legacy"), "react").unwrap(),
+            &VersionProtocol::Requirement(Range::parse("^17.0.2").unwrap())
+        );
+    }
+
+    #[test]
+    fn links_catalog_deps() {
+        let sandbox = create_sandbox("graph-mono-pnpm-catalogs");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert_eq!(
+            graph.dependencies_of("c").unwrap(),
+            vec![
+                ("b".into(), DependencyType::Development),
+                ("a".into(), DependencyType::Production)
+            ]
+        );
+    }
+
+    #[test]
+    fn resolves_catalog_references() {
+        let sandbox = create_sandbox("graph-mono-pnpm-catalogs");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let manifest = graph.resolve_catalog_references("c").unwrap();
+
+        assert_eq!(
+            manifest.dependencies.unwrap().get("react").unwrap(),
+            &VersionProtocol::Requirement(Range::parse("^18.2.0").unwrap())
+        );
+        assert_eq!(
+            manifest.dev_dependencies.unwrap().get("b").unwrap(),
+            &VersionProtocol::Requirement(Range::parse("^2.0.0").unwrap())
+        );
+    }
+
+    #[test]
+    fn errors_for_unknown_catalog() {
+        let sandbox = create_sandbox("graph-mono-pnpm-catalogs");
+        sandbox.create_file(
+            "packages/d/package.json",
+            r#"{ "name": "d", "dependencies": { "a": "catalog:unknown" } }"#,
+        );
+
+        let mut graph = PackageGraph::load_from(sandbox.path()).unwrap();
+        graph.load_workspace_packages().unwrap();
+
+        assert!(matches!(
+            graph.resolve_catalog_references("d").unwrap_err(),
+            PackageGraphError::UnknownCatalog(name) if name == "unknown"
+        ));
+    }
+
+    #[test]
+    fn errors_for_unresolved_reference_when_generating() {
+        let sandbox = create_sandbox("graph-mono-pnpm-catalogs");
+        sandbox.create_file(
+            "packages/d/package.json",
+            r#"{ "name": "d", "devDependencies": { "b": "catalog:" } }"#,
+        );
+
+        let Err(error) = PackageGraph::generate(sandbox.path()) else {
+            panic!("expected an error");
+        };
+
+        assert_eq!(
+            error.to_string(),
+            "Package `d` depends on `b` from catalog `default`, but the catalog does not define it."
+        );
+    }
+
+    #[test]
+    fn errors_for_missing_catalog_entry() {
+        let sandbox = create_sandbox("graph-mono-pnpm-catalogs");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert!(matches!(
+            graph.get_catalog_version(None, "b").unwrap_err(),
+            PackageGraphError::MissingCatalogEntry { catalog, name } if catalog == "default" && name == "b"
+        ));
+    }
+}
+
+mod publishing {
+    use super::*;
+    use nodejs_package_json::{
+        BinField, ImportExportField, PublishAccess, Range, Version, VersionProtocol,
+    };
+    use std::path::PathBuf;
+
+    fn requirement(value: &str) -> VersionProtocol {
+        VersionProtocol::Requirement(Range::parse(value).unwrap())
+    }
+
+    #[test]
+    fn replaces_workspace_references() {
+        let sandbox = create_sandbox("graph-mono-publish");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let manifest = graph.create_publish_manifest("pub").unwrap();
+
+        let deps = manifest.dependencies.unwrap();
+
+        assert_eq!(
+            deps.get("a").unwrap(),
+            &VersionProtocol::Version(Version::new(1, 2, 0))
+        );
+        assert_eq!(deps.get("b").unwrap(), &requirement("^2.0.0"));
+        assert_eq!(deps.get("react").unwrap(), &requirement("^18.2.0"));
+
+        let dev_deps = manifest.dev_dependencies.unwrap();
+
+        assert_eq!(
+            dev_deps.get("a-alias").unwrap(),
+            &VersionProtocol::Alias {
+                package: "a".into(),
+                requirement: Some(Box::new(requirement("~1.2.0"))),
+            }
+        );
+        assert_eq!(
+            dev_deps.get("b-path").unwrap(),
+            &VersionProtocol::Version(Version::new(2, 0, 0))
+        );
+
+        let peer_deps = manifest.peer_dependencies.unwrap();
+
+        assert_eq!(peer_deps.get("a").unwrap(), &requirement("^1.0.0"));
+        assert_eq!(
+            peer_deps.get("b").unwrap(),
+            &VersionProtocol::Version(Version::new(2, 0, 0))
+        );
+    }
+
+    #[test]
+    fn applies_publish_config() {
+        let sandbox = create_sandbox("graph-mono-publish");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+        let manifest = graph.create_publish_manifest("pub").unwrap();
+
+        assert_eq!(manifest.main.unwrap(), PathBuf::from("./dist/index.js"));
+        assert_eq!(manifest.types.unwrap(), PathBuf::from("./dist/index.d.ts"));
+        assert_eq!(
+            manifest.bin.unwrap(),
+            BinField::String(PathBuf::from("./dist/cli.js"))
+        );
+        assert!(matches!(
+            manifest.exports.unwrap(),
+            ImportExportField::Map(_)
+        ));
+        assert_eq!(
+            manifest.other_fields.get("unpkg").unwrap(),
+            "./dist/index.umd.js"
+        );
+        assert!(!manifest.other_fields.contains_key("unknown"));
+
+        let config = manifest.publish_config.unwrap();
+
+        assert_eq!(config.access.unwrap(), PublishAccess::Public);
+        assert_eq!(config.registry.unwrap(), "https://registry.example.com");
+    }
+
+    #[test]
+    fn doesnt_modify_the_original() {
+        let sandbox = create_sandbox("graph-mono-publish");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        graph.create_publish_manifest("pub").unwrap();
+
+        let manifest = &graph.packages.get("pub").unwrap().manifest;
+
+        assert_eq!(
+            manifest.main.as_ref().unwrap(),
+            &PathBuf::from("./src/index.ts")
+        );
+        assert!(matches!(
+            manifest.dependencies.as_ref().unwrap().get("a").unwrap(),
+            VersionProtocol::Workspace(_)
+        ));
+    }
+
+    #[test]
+    fn errors_for_missing_version() {
+        let sandbox = create_sandbox("graph-mono-publish");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert!(matches!(
+            graph.create_publish_manifest("broken").unwrap_err(),
+            PackageGraphError::MissingPackageVersion(name) if name == "c"
+        ));
+    }
+
+    #[test]
+    fn errors_for_unknown_package() {
+        let sandbox = create_sandbox("graph-mono-publish");
+        let graph = PackageGraph::generate(sandbox.path()).unwrap();
+
+        assert!(matches!(
+            graph.create_publish_manifest("unknown").unwrap_err(),
+            PackageGraphError::UnknownPackage(name) if name == "unknown"
+        ));
+    }
+}
+
 mod monorepo_yarn {
     use super::*;
 
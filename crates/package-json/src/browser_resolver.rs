@@ -0,0 +1,152 @@
+// Based on the `browser` field spec, and the behavior of browserify and webpack.
+// https://github.com/defunctzombie/package-browser-field-spec
+
+use crate::package_json::{BrowserField, PackageJson};
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BrowserMapping {
+    // false
+    Disabled,
+    // ./file.js, relative to the package root
+    File(PathBuf),
+    // module or module/subpath
+    Module(String),
+}
+
+impl BrowserMapping {
+    fn from_value(value: &serde_json::Value) -> Option<Self> {
+        match value {
+            serde_json::Value::Bool(false) => Some(Self::Disabled),
+            serde_json::Value::String(value) if is_relative(value) => {
+                Some(Self::File(normalize_relative(value)))
+            }
+            serde_json::Value::String(value) => Some(Self::Module(value.to_owned())),
+            _ => None,
+        }
+    }
+}
+
+fn is_relative(request: &str) -> bool {
+    request == "." || request == ".." || request.starts_with("./") || request.starts_with("../")
+}
+
+// Removes `.` and `..` segments, so that `./lib/../index.js` becomes `index.js`.
+fn normalize_relative(path: &str) -> PathBuf {
+    let mut result = PathBuf::new();
+
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(part) => result.push(part),
+            Component::ParentDir => {
+                result.pop();
+            }
+            _ => {}
+        };
+    }
+
+    result
+}
+
+// Files are matched in the order a bundler would attempt them:
+// as-is, with an extension, and then as a directory index.
+fn get_candidates(path: &Path, extensions: &[&str]) -> Vec<PathBuf> {
+    let mut candidates = vec![path.to_path_buf()];
+    let value = path.to_string_lossy();
+
+    for ext in extensions {
+        candidates.push(PathBuf::from(format!("{value}{ext}")));
+    }
+
+    for ext in extensions {
+        candidates.push(path.join(format!("index{ext}")));
+    }
+
+    candidates
+}
+
+fn matches_key(key: &Path, candidate: &Path, extensions: &[&str]) -> bool {
+    if key == candidate {
+        return true;
+    }
+
+    // Keys may also omit the extension
+    let key = key.to_string_lossy();
+    let candidate = candidate.to_string_lossy();
+
+    extensions
+        .iter()
+        .any(|ext| candidate.strip_suffix(ext) == Some(key.as_ref()))
+}
+
+impl BrowserField {
+    // Returns the replacement for a request, which is either a bare module
+    // or a file relative to the package root (`./lib/file.js`). Extensions
+    // are used to match requests and keys that omit them, for example `.js`.
+    pub fn resolve_request(&self, request: &str, extensions: &[&str]) -> Option<BrowserMapping> {
+        let BrowserField::Map(map) = self else {
+            return None;
+        };
+
+        if !is_relative(request) {
+            return map
+                .iter()
+                .find(|(key, _)| key.as_os_str() == request)
+                .and_then(|(_, value)| BrowserMapping::from_value(value));
+        }
+
+        let path = normalize_relative(request);
+
+        // Keys with and without a leading `./` are equivalent
+        let keys = map
+            .iter()
+            .map(|(key, value)| (normalize_relative(&key.to_string_lossy()), value))
+            .collect::<Vec<_>>();
+
+        for candidate in get_candidates(&path, extensions) {
+            for (key, value) in &keys {
+                if matches_key(key, &candidate, extensions) {
+                    return BrowserMapping::from_value(value);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl PackageJson {
+    // Applies the `browser` field to a request, returning the replacement,
+    // `BrowserMapping::Disabled` when the module is replaced with `false`,
+    // or `None` when there's no mapping. The package's main entry point
+    // can be requested with `.`.
+    pub fn resolve_browser(&self, request: &str, extensions: &[&str]) -> Option<BrowserMapping> {
+        let browser = self.browser.as_ref()?;
+
+        // The main entry point may be replaced with a string, or by mapping
+        // the file that the `main` field points to
+        let main = normalize_relative(
+            &self
+                .main
+                .as_ref()
+                .map(|main| main.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "index.js".into()),
+        );
+
+        match browser {
+            BrowserField::String(replacement) => {
+                let is_main = request == "."
+                    || (is_relative(request)
+                        && get_candidates(&normalize_relative(request), extensions)
+                            .iter()
+                            .any(|candidate| matches_key(&main, candidate, extensions)));
+
+                is_main.then(|| BrowserMapping::File(normalize_relative(replacement)))
+            }
+            BrowserField::Map(_) if request == "." => {
+                browser.resolve_request(&format!("./{}", main.display()), extensions)
+            }
+            BrowserField::Map(_) => browser.resolve_request(request, extensions),
+        }
+    }
+}
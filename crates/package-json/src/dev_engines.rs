@@ -0,0 +1,68 @@
+use crate::range::Range;
+use serde::Deserialize;
+
+// https://docs.npmjs.com/cli/v10/configuring-npm/package-json#devengines
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DevEnginesField {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cpu: Option<DevEngineField>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub libc: Option<DevEngineField>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os: Option<DevEngineField>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package_manager: Option<DevEngineField>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runtime: Option<DevEngineField>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum DevEngineField {
+    One(DevEngine),
+    Many(Vec<DevEngine>),
+}
+
+impl DevEngineField {
+    pub fn to_list(&self) -> Vec<&DevEngine> {
+        match self {
+            Self::One(engine) => vec![engine],
+            Self::Many(engines) => engines.iter().collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DevEngine {
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<Range>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_fail: Option<DevEngineOnFail>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum DevEngineOnFail {
+    Download,
+    #[default]
+    Error,
+    Ignore,
+    Warn,
+}
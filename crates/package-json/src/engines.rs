@@ -0,0 +1,76 @@
+use crate::package_json::PackageJson;
+use crate::range::Range;
+use crate::FxIndexMap;
+use semver::Version;
+
+// Versions of the current runtime and package manager,
+// keyed by engine name (node, npm, pnpm, etc).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RuntimeVersions {
+    versions: FxIndexMap<String, Version>,
+}
+
+impl RuntimeVersions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<T: AsRef<str>>(mut self, engine: T, version: Version) -> Self {
+        self.set(engine, version);
+        self
+    }
+
+    pub fn get(&self, engine: &str) -> Option<&Version> {
+        self.versions.get(engine)
+    }
+
+    pub fn set<T: AsRef<str>>(&mut self, engine: T, version: Version) {
+        self.versions.insert(engine.as_ref().to_owned(), version);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EngineCheck {
+    Satisfied,
+    Violated { range: Range, version: Version },
+    // Not a version range, for example a dist-tag
+    Unparseable(String),
+}
+
+impl PackageJson {
+    pub fn get_engine_range(&self, engine: &str) -> Option<Range> {
+        self.engines
+            .as_ref()?
+            .get(engine)
+            .and_then(|value| Range::parse(value.to_string()).ok())
+    }
+
+    // Checks each engine that has a runtime version. Engines without
+    // a runtime version are not checked, and are not returned.
+    pub fn check_engines(&self, runtimes: &RuntimeVersions) -> FxIndexMap<String, EngineCheck> {
+        let mut checks = FxIndexMap::default();
+
+        let Some(engines) = &self.engines else {
+            return checks;
+        };
+
+        for (engine, value) in engines {
+            let Some(version) = runtimes.get(engine) else {
+                continue;
+            };
+
+            let check = match Range::parse(value.to_string()) {
+                Ok(range) if range.satisfies(version) => EngineCheck::Satisfied,
+                Ok(range) => EngineCheck::Violated {
+                    range,
+                    version: version.to_owned(),
+                },
+                Err(_) => EngineCheck::Unparseable(value.to_string()),
+            };
+
+            checks.insert(engine.to_owned(), check);
+        }
+
+        checks
+    }
+}
@@ -0,0 +1,215 @@
+use crate::import_export::*;
+use crate::import_export_resolver::ImportExportError;
+use crate::normalize::walk_files;
+use crate::package_json::{BrowserField, PackageJson};
+use std::path::{Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct EntryPoint {
+    // Conditions that must be matched, in order, to reach the target.
+    // Legacy fields use the field name (`module`, `browser`, `types`) as
+    // the condition, while `main` has none.
+    pub conditions: Vec<String>,
+
+    // Key in the `exports` map that defines the entry point.
+    pub key: ImportExportKey,
+
+    // Resolved file path, relative to the package root.
+    pub path: PathBuf,
+
+    // Public subpath that can be imported, for example `.` or `./utils`.
+    pub subpath: String,
+}
+
+// A target and the conditions to reach it, before patterns are expanded.
+struct ExportTarget {
+    conditions: Vec<String>,
+    target: String,
+}
+
+fn collect_targets(
+    field: &ImportExportField,
+    conditions: &mut Vec<String>,
+    targets: &mut Vec<ExportTarget>,
+) {
+    match field {
+        ImportExportField::None => {}
+        ImportExportField::String(target) => {
+            targets.push(ExportTarget {
+                conditions: conditions.clone(),
+                target: target.to_owned(),
+            });
+        }
+        ImportExportField::Array(list) => {
+            // Only the first valid fallback is ever used
+            for item in list {
+                if let ImportExportField::String(target) = item {
+                    if target.starts_with("./") {
+                        collect_targets(item, conditions, targets);
+                        break;
+                    }
+                } else {
+                    collect_targets(item, conditions, targets);
+                }
+            }
+        }
+        ImportExportField::Map(map) => {
+            for (key, value) in map {
+                let condition = key.to_string();
+                let is_default = condition == "default";
+
+                conditions.push(condition);
+                collect_targets(value, conditions, targets);
+                conditions.pop();
+
+                // Conditions after the default are unreachable
+                if is_default {
+                    break;
+                }
+            }
+        }
+    }
+}
+
+fn expand_pattern(root: &Path, key: &str, target: &str) -> Vec<(String, String)> {
+    let target = target.trim_start_matches("./");
+
+    let Some((prefix, suffix)) = target.split_once('*') else {
+        return vec![];
+    };
+
+    let mut files = vec![];
+    let dir = prefix.rfind('/').map(|i| &prefix[..i]).unwrap_or_default();
+
+    walk_files(root, Path::new(dir), &mut files);
+
+    files
+        .into_iter()
+        .filter_map(|file| {
+            let file = file.to_string_lossy().replace('\\', "/");
+
+            if file.len() <= prefix.len() + suffix.len()
+                || !file.starts_with(prefix)
+                || !file.ends_with(suffix)
+            {
+                return None;
+            }
+
+            let pattern_match = &file[prefix.len()..file.len() - suffix.len()];
+
+            Some((key.replace('*', pattern_match), format!("./{file}")))
+        })
+        .collect()
+}
+
+impl ImportExportField {
+    // Lists every subpath exposed by the package, for every combination of
+    // conditions. Patterns are expanded against the files in the root, and
+    // entry points that are excluded or shadowed are omitted.
+    pub fn get_entry_points(&self, root: &Path) -> Result<Vec<EntryPoint>, ImportExportError> {
+        let keys = match self {
+            ImportExportField::Map(map)
+                if map
+                    .keys()
+                    .any(|key| !matches!(key, ImportExportKey::CustomCondition(_))) =>
+            {
+                map.iter()
+                    .map(|(key, value)| (key.to_string(), value))
+                    .collect::<Vec<_>>()
+            }
+            _ => vec![(".".to_owned(), self)],
+        };
+
+        let mut entry_points = vec![];
+
+        for (key, value) in keys {
+            let mut targets = vec![];
+            collect_targets(value, &mut vec![], &mut targets);
+
+            for ExportTarget { conditions, target } in targets {
+                let subpaths = if key.contains('*') {
+                    expand_pattern(root, &key, &target)
+                } else {
+                    vec![(key.clone(), target)]
+                };
+
+                for (subpath, target) in subpaths {
+                    let condition_names = conditions.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+
+                    // Verify against the resolver, so that precedence and
+                    // exclusions are applied the same way Node.js does
+                    let resolved = match self.resolve_export(&subpath, &condition_names) {
+                        Ok(resolved) => resolved,
+                        Err(ImportExportError::InvalidConfig) => {
+                            return Err(ImportExportError::InvalidConfig);
+                        }
+                        Err(_) => continue,
+                    };
+
+                    if resolved.conditions != conditions
+                        || resolved.path != Path::new(target.trim_start_matches("./"))
+                    {
+                        continue;
+                    }
+
+                    entry_points.push(EntryPoint {
+                        conditions: resolved.conditions,
+                        key: resolved.key,
+                        path: resolved.path,
+                        subpath,
+                    });
+                }
+            }
+        }
+
+        Ok(entry_points)
+    }
+}
+
+impl PackageJson {
+    // Lists every public entry point of the package. When `exports` is not
+    // defined, the legacy `main`, `module`, `browser`, and `types` fields
+    // are used instead, falling back to `index.js` if it exists in `root`.
+    pub fn get_entry_points(&self, root: &Path) -> Result<Vec<EntryPoint>, ImportExportError> {
+        if let Some(exports) = &self.exports {
+            return exports.get_entry_points(root);
+        }
+
+        let mut entry_points = vec![];
+        let mut add = |condition: Option<&str>, path: &Path| {
+            let path = path.strip_prefix("./").unwrap_or(path);
+
+            if path.as_os_str().is_empty() {
+                return;
+            }
+
+            entry_points.push(EntryPoint {
+                conditions: condition.map(|c| vec![c.to_owned()]).unwrap_or_default(),
+                key: ImportExportKey::Main,
+                path: path.to_path_buf(),
+                subpath: ".".into(),
+            });
+        };
+
+        match &self.main {
+            Some(main) => add(None, main),
+            // Node.js falls back to `index.js` when there's no main
+            None if root.join("index.js").is_file() => add(None, Path::new("index.js")),
+            None => {}
+        };
+
+        if let Some(module) = &self.module {
+            add(Some("module"), module);
+        }
+
+        if let Some(BrowserField::String(browser)) = &self.browser {
+            add(Some("browser"), Path::new(browser));
+        }
+
+        if let Some(types) = self.types.as_ref().or(self.typings.as_ref()) {
+            add(Some("types"), types);
+        }
+
+        Ok(entry_points)
+    }
+}
@@ -6,6 +6,7 @@ pub type ImportExportMap = FxIndexMap<ImportExportKey, ImportExportField>;
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum ImportExportField {
     #[default]
@@ -0,0 +1,409 @@
+// Based on the Node.js resolution algorithm specification.
+// https://nodejs.org/api/esm.html#resolution-algorithm-specification
+
+use crate::import_export::*;
+use crate::package_json::PackageJson;
+use std::cmp::Ordering;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Debug, Error, PartialEq)]
+#[cfg_attr(feature = "miette", derive(miette::Diagnostic))]
+pub enum ImportExportError {
+    #[error("Invalid package configuration, cannot mix subpath keys (starting with `.`) and condition keys in the same map.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::import_export::invalid_config))
+    )]
+    InvalidConfig,
+
+    #[error(
+        "Invalid import specifier `{0}`, must start with `#` and cannot be `#` or start with `#/`."
+    )]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::import_export::invalid_specifier))
+    )]
+    InvalidSpecifier(String),
+
+    #[error("Invalid subpath `{0}`, cannot contain `.`, `..`, or `node_modules` segments.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::import_export::invalid_subpath))
+    )]
+    InvalidSubpath(String),
+
+    #[error("Invalid target `{target}` for key `{key}`.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(
+            code(package_json::import_export::invalid_target),
+            help = "Targets must be relative paths that start with `./`. Imports may also target a package."
+        )
+    )]
+    InvalidTarget { key: String, target: String },
+
+    #[error("Import `{0}` is not defined by the package.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::import_export::not_defined))
+    )]
+    NotDefined(String),
+
+    #[error("Subpath `{0}` is not exported by the package.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::import_export::not_exported))
+    )]
+    NotExported(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolvedExport {
+    // Conditions that were matched, in order, to reach the target.
+    pub conditions: Vec<String>,
+
+    // Key in the `exports` map that matched the subpath.
+    pub key: ImportExportKey,
+
+    // Resolved file path, relative to the package root.
+    pub path: PathBuf,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ImportTarget {
+    // ./file.js
+    File(PathBuf),
+    // package or package/subpath, that must be resolved again
+    Package(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ResolvedImport {
+    // Conditions that were matched, in order, to reach the target.
+    pub conditions: Vec<String>,
+
+    // Key in the `imports` map that matched the specifier.
+    pub key: ImportExportKey,
+
+    pub target: ImportTarget,
+}
+
+// Node.js differentiates between `null` (excluded) and
+// `undefined` (no condition matched) while resolving.
+enum Resolution {
+    Found(String),
+    Excluded,
+    Unmatched,
+}
+
+struct TargetResolver<'a> {
+    conditions: &'a [&'a str],
+    is_imports: bool,
+    key: String,
+    matched_conditions: Vec<String>,
+}
+
+impl<'a> TargetResolver<'a> {
+    fn new(key: String, conditions: &'a [&'a str], is_imports: bool) -> Self {
+        Self {
+            conditions,
+            is_imports,
+            key,
+            matched_conditions: vec![],
+        }
+    }
+
+    fn resolve(
+        &mut self,
+        target: &ImportExportField,
+        pattern_match: Option<&str>,
+    ) -> Result<Resolution, ImportExportError> {
+        match target {
+            ImportExportField::None => Ok(Resolution::Excluded),
+            ImportExportField::String(value) => self.resolve_string(value, pattern_match),
+            ImportExportField::Map(map) => {
+                for (key, value) in map {
+                    let condition = key.to_string();
+
+                    if condition != "default" && !self.conditions.contains(&condition.as_str()) {
+                        continue;
+                    }
+
+                    let depth = self.matched_conditions.len();
+                    self.matched_conditions.push(condition);
+
+                    match self.resolve(value, pattern_match)? {
+                        Resolution::Unmatched => {
+                            self.matched_conditions.truncate(depth);
+                        }
+                        result => return Ok(result),
+                    };
+                }
+
+                Ok(Resolution::Unmatched)
+            }
+            ImportExportField::Array(list) => {
+                if list.is_empty() {
+                    return Ok(Resolution::Excluded);
+                }
+
+                let mut last_result = None;
+
+                for item in list {
+                    let depth = self.matched_conditions.len();
+
+                    match self.resolve(item, pattern_match) {
+                        Ok(Resolution::Found(path)) => return Ok(Resolution::Found(path)),
+                        Ok(Resolution::Excluded) => {
+                            last_result = Some(Ok(Resolution::Excluded));
+                        }
+                        Ok(Resolution::Unmatched) => {}
+                        Err(error @ ImportExportError::InvalidTarget { .. }) => {
+                            last_result = Some(Err(error));
+                        }
+                        Err(error) => return Err(error),
+                    };
+
+                    self.matched_conditions.truncate(depth);
+                }
+
+                last_result.unwrap_or(Ok(Resolution::Unmatched))
+            }
+        }
+    }
+
+    fn resolve_string(
+        &self,
+        target: &str,
+        pattern_match: Option<&str>,
+    ) -> Result<Resolution, ImportExportError> {
+        let invalid_target = || ImportExportError::InvalidTarget {
+            key: self.key.clone(),
+            target: target.to_owned(),
+        };
+
+        if !target.starts_with("./") {
+            // Only imports can map to another package
+            if !self.is_imports
+                || target.starts_with("../")
+                || target.starts_with('/')
+                || is_url(target)
+            {
+                return Err(invalid_target());
+            }
+
+            return Ok(Resolution::Found(match pattern_match {
+                Some(pattern_match) => target.replace('*', pattern_match),
+                None => target.to_owned(),
+            }));
+        }
+
+        if has_invalid_segments(&target[2..]) {
+            return Err(invalid_target());
+        }
+
+        let Some(pattern_match) = pattern_match else {
+            return Ok(Resolution::Found(target.to_owned()));
+        };
+
+        if has_invalid_segments(pattern_match) {
+            return Err(ImportExportError::InvalidSubpath(
+                self.key.replace('*', pattern_match),
+            ));
+        }
+
+        Ok(Resolution::Found(target.replace('*', pattern_match)))
+    }
+}
+
+// Empty segments are deprecated but still allowed by Node.js.
+fn has_invalid_segments(path: &str) -> bool {
+    path.split(['/', '\\']).any(|segment| {
+        let segment = segment
+            .to_lowercase()
+            .replace("%2e", ".")
+            .replace("%5f", "_");
+
+        segment == "." || segment == ".." || segment == "node_modules"
+    })
+}
+
+fn is_url(value: &str) -> bool {
+    value.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.len() > 1
+            && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+    })
+}
+
+fn pattern_key_compare(a: &str, b: &str) -> Ordering {
+    let a_base = a.find('*').map(|i| i + 1).unwrap_or(a.len());
+    let b_base = b.find('*').map(|i| i + 1).unwrap_or(b.len());
+
+    b_base
+        .cmp(&a_base)
+        .then_with(|| match (a.contains('*'), b.contains('*')) {
+            (false, _) => Ordering::Greater,
+            (_, false) => Ordering::Less,
+            _ => b.len().cmp(&a.len()),
+        })
+}
+
+fn has_subpath_keys(map: &ImportExportMap) -> Result<bool, ImportExportError> {
+    let mut subpaths = 0;
+
+    for key in map.keys() {
+        if matches!(key, ImportExportKey::Main)
+            || matches!(key, ImportExportKey::Pattern(pat) if !pat.starts_with('#'))
+        {
+            subpaths += 1;
+        }
+    }
+
+    if subpaths > 0 && subpaths != map.len() {
+        return Err(ImportExportError::InvalidConfig);
+    }
+
+    Ok(subpaths > 0)
+}
+
+// PACKAGE_IMPORTS_EXPORTS_RESOLVE
+fn resolve_match_key<'a>(
+    match_key: &str,
+    map: &ImportExportMap,
+    conditions: &'a [&'a str],
+    is_imports: bool,
+) -> Result<Option<(TargetResolver<'a>, String)>, ImportExportError> {
+    // Exact matches take precedence over patterns
+    if !match_key.contains('*') {
+        if let Some(target) = map.get(&ImportExportKey::from(match_key)) {
+            let mut resolver = TargetResolver::new(match_key.to_owned(), conditions, is_imports);
+
+            return Ok(match resolver.resolve(target, None)? {
+                Resolution::Found(path) => Some((resolver, path)),
+                _ => None,
+            });
+        }
+    }
+
+    let mut expansion_keys = map
+        .iter()
+        .map(|(key, target)| (key.to_string(), target))
+        .filter(|(key, _)| key.matches('*').count() == 1)
+        .collect::<Vec<_>>();
+
+    expansion_keys.sort_by(|a, b| pattern_key_compare(&a.0, &b.0));
+
+    for (key, target) in expansion_keys {
+        let (base, trailer) = key.split_once('*').unwrap();
+
+        if match_key.starts_with(base)
+            && match_key != base
+            && (trailer.is_empty() || match_key.ends_with(trailer) && match_key.len() >= key.len())
+        {
+            let pattern_match = &match_key[base.len()..match_key.len() - trailer.len()];
+            let mut resolver = TargetResolver::new(key.clone(), conditions, is_imports);
+
+            return Ok(match resolver.resolve(target, Some(pattern_match))? {
+                Resolution::Found(path) => Some((resolver, path)),
+                _ => None,
+            });
+        }
+    }
+
+    Ok(None)
+}
+
+fn to_resolved_export(resolver: TargetResolver, path: String) -> ResolvedExport {
+    ResolvedExport {
+        conditions: resolver.matched_conditions,
+        key: ImportExportKey::from(resolver.key),
+        path: PathBuf::from(path.strip_prefix("./").unwrap_or(&path)),
+    }
+}
+
+impl ImportExportField {
+    // https://nodejs.org/api/esm.html#resolution-algorithm-specification
+    // PACKAGE_EXPORTS_RESOLVE
+    pub fn resolve_export(
+        &self,
+        subpath: &str,
+        conditions: &[&str],
+    ) -> Result<ResolvedExport, ImportExportError> {
+        let subpath = match subpath {
+            "" | "." => ".".to_owned(),
+            sub if sub.starts_with("./") => sub.to_owned(),
+            sub => format!("./{sub}"),
+        };
+
+        let resolved = match self {
+            ImportExportField::Map(map) if has_subpath_keys(map)? => {
+                resolve_match_key(&subpath, map, conditions, false)?
+            }
+            _ if subpath == "." => {
+                let mut resolver = TargetResolver::new(subpath.clone(), conditions, false);
+
+                match resolver.resolve(self, None)? {
+                    Resolution::Found(path) => Some((resolver, path)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+
+        match resolved {
+            Some((resolver, path)) => Ok(to_resolved_export(resolver, path)),
+            None => Err(ImportExportError::NotExported(subpath)),
+        }
+    }
+}
+
+// PACKAGE_IMPORTS_RESOLVE
+pub fn resolve_import(
+    imports: &ImportExportMap,
+    specifier: &str,
+    conditions: &[&str],
+) -> Result<ResolvedImport, ImportExportError> {
+    if !specifier.starts_with('#') || specifier == "#" || specifier.starts_with("#/") {
+        return Err(ImportExportError::InvalidSpecifier(specifier.to_owned()));
+    }
+
+    match resolve_match_key(specifier, imports, conditions, true)? {
+        Some((resolver, target)) => Ok(ResolvedImport {
+            conditions: resolver.matched_conditions,
+            key: ImportExportKey::from(resolver.key),
+            target: match target.strip_prefix("./") {
+                Some(path) => ImportTarget::File(PathBuf::from(path)),
+                None => ImportTarget::Package(target),
+            },
+        }),
+        None => Err(ImportExportError::NotDefined(specifier.to_owned())),
+    }
+}
+
+impl PackageJson {
+    pub fn resolve_export(
+        &self,
+        subpath: &str,
+        conditions: &[&str],
+    ) -> Result<ResolvedExport, ImportExportError> {
+        match &self.exports {
+            Some(exports) => exports.resolve_export(subpath, conditions),
+            None => Err(ImportExportError::NotExported(subpath.to_owned())),
+        }
+    }
+
+    pub fn resolve_import(
+        &self,
+        specifier: &str,
+        conditions: &[&str],
+    ) -> Result<ResolvedImport, ImportExportError> {
+        match &self.imports {
+            Some(imports) => resolve_import(imports, specifier, conditions),
+            None => Err(ImportExportError::NotDefined(specifier.to_owned())),
+        }
+    }
+}
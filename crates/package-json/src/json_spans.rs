@@ -0,0 +1,134 @@
+use rustc_hash::FxHashMap;
+
+// Byte offset and length of a value within the source.
+pub type JsonSpan = (usize, usize);
+
+// Maps a JSON pointer (RFC 6901) of every value to its byte span.
+// The content must be valid JSON, so parse it with serde first.
+pub fn index_json_spans(content: &str) -> FxHashMap<String, JsonSpan> {
+    let mut indexer = JsonSpanIndexer {
+        bytes: content.as_bytes(),
+        content,
+        index: 0,
+        spans: FxHashMap::default(),
+    };
+
+    indexer.value(String::new());
+    indexer.spans
+}
+
+pub fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+struct JsonSpanIndexer<'a> {
+    bytes: &'a [u8],
+    content: &'a str,
+    index: usize,
+    spans: FxHashMap<String, JsonSpan>,
+}
+
+impl<'a> JsonSpanIndexer<'a> {
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.index).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(|b| b.is_ascii_whitespace()) {
+            self.index += 1;
+        }
+    }
+
+    fn value(&mut self, pointer: String) {
+        self.skip_whitespace();
+
+        let start = self.index;
+
+        match self.peek() {
+            Some(b'{') => self.object(&pointer),
+            Some(b'[') => self.array(&pointer),
+            Some(b'"') => self.string(),
+            Some(_) => {
+                while self
+                    .peek()
+                    .is_some_and(|b| !matches!(b, b',' | b'}' | b']') && !b.is_ascii_whitespace())
+                {
+                    self.index += 1;
+                }
+            }
+            None => return,
+        };
+
+        self.spans.insert(pointer, (start, self.index - start));
+    }
+
+    fn object(&mut self, pointer: &str) {
+        self.index += 1; // {
+
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(b'"') => {
+                    let start = self.index;
+                    self.string();
+
+                    let key: String =
+                        serde_json::from_str(&self.content[start..self.index]).unwrap_or_default();
+
+                    self.skip_whitespace();
+                    self.index += 1; // :
+
+                    self.value(format!("{pointer}/{}", escape_pointer_token(&key)));
+                }
+                Some(b',') => {
+                    self.index += 1;
+                }
+                Some(b'}') => {
+                    self.index += 1;
+                    break;
+                }
+                _ => break,
+            };
+        }
+    }
+
+    fn array(&mut self, pointer: &str) {
+        self.index += 1; // [
+
+        let mut item = 0;
+
+        loop {
+            self.skip_whitespace();
+
+            match self.peek() {
+                Some(b',') => {
+                    self.index += 1;
+                }
+                Some(b']') => {
+                    self.index += 1;
+                    break;
+                }
+                Some(_) => {
+                    self.value(format!("{pointer}/{item}"));
+                    item += 1;
+                }
+                None => break,
+            };
+        }
+    }
+
+    fn string(&mut self) {
+        self.index += 1; // "
+
+        while let Some(b) = self.peek() {
+            self.index += 1;
+
+            match b {
+                b'\\' => self.index += 1,
+                b'"' => break,
+                _ => {}
+            };
+        }
+    }
+}
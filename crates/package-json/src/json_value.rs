@@ -0,0 +1,32 @@
+use indexmap::IndexMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Number;
+
+pub type JsonMap = IndexMap<String, JsonValue>;
+
+// A JSON value that always preserves the key order of objects. This avoids
+// relying on the `preserve_order` feature of `serde_json`, which would be
+// enabled for every crate in the build through feature unification.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(JsonMap),
+}
+
+impl JsonValue {
+    // Round-trip through a string, as `serde_json::Value`
+    // does not preserve the key order of nested objects.
+    pub fn from_serialize<T: Serialize>(value: &T) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(&serde_json::to_string(value)?)
+    }
+
+    pub fn to_deserialize<T: DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&serde_json::to_string(self)?)
+    }
+}
@@ -1,11 +1,55 @@
+mod browser_resolver;
+mod dev_engines;
+mod engines;
+mod entry_points;
 mod import_export;
+mod import_export_resolver;
+mod json_spans;
+mod json_value;
+mod metadata_fields;
+mod normalize;
+mod overrides;
 mod package_json;
+#[cfg(feature = "editor")]
+mod package_json_editor;
+#[cfg(feature = "packlist")]
+mod packlist;
 mod protocols;
+mod publish_config;
+mod range;
+#[cfg(feature = "schema")]
+mod schema;
+mod side_effects;
+#[cfg(feature = "globs")]
+mod side_effects_matcher;
+mod types_resolver;
+mod validation;
 
+pub use browser_resolver::*;
+pub use dev_engines::*;
+pub use engines::*;
+pub use entry_points::*;
 pub use import_export::*;
+pub use import_export_resolver::*;
+#[cfg(feature = "editor")]
+pub use json_value::{JsonMap, JsonValue};
+pub use metadata_fields::*;
+pub use normalize::*;
+pub use overrides::*;
 pub use package_json::*;
+#[cfg(feature = "editor")]
+pub use package_json_editor::*;
+#[cfg(feature = "packlist")]
+pub use packlist::*;
 pub use protocols::*;
+pub use publish_config::*;
+pub use range::*;
 pub use semver::{Version, VersionReq};
+pub use side_effects::*;
+#[cfg(feature = "globs")]
+pub use side_effects_matcher::*;
+pub use types_resolver::*;
+pub use validation::*;
 
 use indexmap::IndexMap;
 use rustc_hash::FxHasher;
@@ -0,0 +1,355 @@
+use crate::json_value::JsonValue;
+use crate::FxIndexMap;
+use rustc_hash::FxHashMap;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+// Metadata fields are loosely validated by package managers, so an invalid
+// value is treated as missing, instead of failing the entire manifest.
+pub(crate) fn lenient<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = JsonValue::deserialize(deserializer)?;
+
+    Ok(value.to_deserialize().ok())
+}
+
+// Like `lenient`, but drops invalid items instead of the entire list.
+pub(crate) fn lenient_list<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let JsonValue::Array(items) = JsonValue::deserialize(deserializer)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(
+        items
+            .into_iter()
+            .filter_map(|item| item.to_deserialize().ok())
+            .collect(),
+    ))
+}
+
+// https://docs.npmjs.com/cli/v10/configuring-npm/package-json#bin
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum BinField {
+    String(PathBuf),
+    Map(FxIndexMap<String, PathBuf>),
+}
+
+// https://docs.npmjs.com/cli/v10/configuring-npm/package-json#bundledependencies
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum BundleDependenciesField {
+    // `true` bundles all dependencies
+    Enabled(bool),
+    List(Vec<String>),
+}
+
+// https://docs.npmjs.com/cli/v10/configuring-npm/package-json#bugs
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum BugsField {
+    Url(String),
+    Object {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        email: Option<String>,
+    },
+}
+
+// https://docs.npmjs.com/cli/v10/configuring-npm/package-json#directories
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct DirectoriesField {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bin: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub man: Option<PathBuf>,
+
+    #[serde(flatten)]
+    pub other_fields: FxHashMap<String, serde_json::Value>,
+}
+
+// https://docs.npmjs.com/cli/v10/configuring-npm/package-json#funding
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum FundingField {
+    Url(String),
+    Object {
+        #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
+        type_of: Option<String>,
+
+        url: String,
+    },
+    List(Vec<FundingField>),
+}
+
+// https://docs.npmjs.com/cli/v10/configuring-npm/package-json#license
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[serde(untagged, from = "LicenseValue", into = "LicenseValue")]
+pub enum LicenseField {
+    // SEE LICENSE IN <file>
+    File(PathBuf),
+    // { "type": "MIT", "url": "..." }
+    Legacy {
+        type_of: String,
+        url: Option<String>,
+    },
+    // MIT, (MIT OR Apache-2.0), etc
+    Spdx(String),
+    // UNLICENSED
+    Unlicensed,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub(crate) enum LicenseValue {
+    String(String),
+    Object {
+        #[serde(rename = "type")]
+        type_of: String,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        url: Option<String>,
+    },
+}
+
+impl From<LicenseValue> for LicenseField {
+    fn from(value: LicenseValue) -> Self {
+        match value {
+            LicenseValue::String(license) => {
+                if license == "UNLICENSED" {
+                    Self::Unlicensed
+                } else if let Some(file) = license.strip_prefix("SEE LICENSE IN ") {
+                    Self::File(PathBuf::from(file.trim()))
+                } else {
+                    Self::Spdx(license)
+                }
+            }
+            LicenseValue::Object { type_of, url } => Self::Legacy { type_of, url },
+        }
+    }
+}
+
+impl From<LicenseField> for LicenseValue {
+    fn from(value: LicenseField) -> Self {
+        match value {
+            LicenseField::File(file) => {
+                LicenseValue::String(format!("SEE LICENSE IN {}", file.display()))
+            }
+            LicenseField::Legacy { type_of, url } => LicenseValue::Object { type_of, url },
+            LicenseField::Spdx(license) => LicenseValue::String(license),
+            LicenseField::Unlicensed => LicenseValue::String("UNLICENSED".into()),
+        }
+    }
+}
+
+// https://docs.npmjs.com/cli/v10/configuring-npm/package-json#man
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum ManField {
+    String(PathBuf),
+    List(Vec<PathBuf>),
+}
+
+// https://docs.npmjs.com/cli/v10/configuring-npm/package-json#people-fields-author-contributors
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[serde(try_from = "PersonValue")]
+pub struct Person {
+    pub name: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub(crate) enum PersonValue {
+    String(String),
+    Object {
+        name: String,
+        email: Option<String>,
+        url: Option<String>,
+    },
+}
+
+impl TryFrom<PersonValue> for Person {
+    type Error = String;
+
+    fn try_from(value: PersonValue) -> Result<Self, Self::Error> {
+        match value {
+            PersonValue::String(person) => Person::from_str(&person),
+            PersonValue::Object { name, email, url } => Ok(Person { name, email, url }),
+        }
+    }
+}
+
+// Name <email> (url)
+impl FromStr for Person {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let extract = |open: char, close: char| {
+            let start = value.find(open)?;
+            let end = value[start..].find(close)? + start;
+            let inner = value[start + 1..end].trim();
+
+            (!inner.is_empty()).then(|| inner.to_owned())
+        };
+
+        let name = value.split(['<', '(']).next().unwrap_or_default().trim();
+
+        if name.is_empty() {
+            return Err(format!("Invalid person `{value}`, a name is required."));
+        }
+
+        Ok(Person {
+            name: name.to_owned(),
+            email: extract('<', '>'),
+            url: extract('(', ')'),
+        })
+    }
+}
+
+impl fmt::Display for Person {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+
+        if let Some(email) = &self.email {
+            write!(f, " <{email}>")?;
+        }
+
+        if let Some(url) = &self.url {
+            write!(f, " ({url})")?;
+        }
+
+        Ok(())
+    }
+}
+
+// https://docs.npmjs.com/cli/v10/configuring-npm/package-json#repository
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[serde(untagged, from = "RepositoryValue", into = "RepositoryValue")]
+pub enum RepositoryField {
+    // github:owner/repo, gitlab:owner/repo, owner/repo, etc
+    Shorthand {
+        host: String,
+        owner: String,
+        repo: String,
+    },
+    Url(String),
+    Object {
+        type_of: Option<String>,
+        url: String,
+        directory: Option<String>,
+    },
+}
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub(crate) enum RepositoryValue {
+    String(String),
+    Object {
+        #[serde(skip_serializing_if = "Option::is_none", rename = "type")]
+        type_of: Option<String>,
+
+        url: String,
+
+        #[serde(skip_serializing_if = "Option::is_none")]
+        directory: Option<String>,
+    },
+}
+
+impl From<RepositoryValue> for RepositoryField {
+    fn from(value: RepositoryValue) -> Self {
+        match value {
+            RepositoryValue::String(repository) => {
+                let (host, path) = match repository.split_once(':') {
+                    Some((host, path))
+                        if matches!(host, "bitbucket" | "github" | "gist" | "gitlab") =>
+                    {
+                        (host, path)
+                    }
+                    Some(_) => return Self::Url(repository),
+                    None => ("github", repository.as_str()),
+                };
+
+                match path.split_once('/') {
+                    Some((owner, repo))
+                        if !owner.is_empty() && !repo.is_empty() && !repo.contains('/') =>
+                    {
+                        Self::Shorthand {
+                            host: host.to_owned(),
+                            owner: owner.to_owned(),
+                            repo: repo.to_owned(),
+                        }
+                    }
+                    _ => Self::Url(repository),
+                }
+            }
+            RepositoryValue::Object {
+                type_of,
+                url,
+                directory,
+            } => Self::Object {
+                type_of,
+                url,
+                directory,
+            },
+        }
+    }
+}
+
+impl From<RepositoryField> for RepositoryValue {
+    fn from(value: RepositoryField) -> Self {
+        match value {
+            RepositoryField::Shorthand { host, owner, repo } => {
+                RepositoryValue::String(format!("{host}:{owner}/{repo}"))
+            }
+            RepositoryField::Url(url) => RepositoryValue::String(url),
+            RepositoryField::Object {
+                type_of,
+                url,
+                directory,
+            } => RepositoryValue::Object {
+                type_of,
+                url,
+                directory,
+            },
+        }
+    }
+}
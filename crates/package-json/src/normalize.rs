@@ -0,0 +1,327 @@
+// Based on npm's `normalize-package-data` and `@npmcli/package-json`.
+// https://github.com/npm/normalize-package-data
+
+use crate::metadata_fields::*;
+use crate::package_json::PackageJson;
+use crate::FxIndexMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct HostedRepository {
+    // bitbucket, gist, github, gitlab
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+}
+
+impl HostedRepository {
+    pub fn domain(&self) -> &str {
+        match self.host.as_str() {
+            "bitbucket" => "bitbucket.org",
+            "gist" => "gist.github.com",
+            "gitlab" => "gitlab.com",
+            _ => "github.com",
+        }
+    }
+
+    pub fn bugs_url(&self) -> String {
+        format!(
+            "https://{}/{}/{}/issues",
+            self.domain(),
+            self.owner,
+            self.repo
+        )
+    }
+
+    pub fn git_url(&self) -> String {
+        format!(
+            "git+https://{}/{}/{}.git",
+            self.domain(),
+            self.owner,
+            self.repo
+        )
+    }
+
+    pub fn homepage_url(&self) -> String {
+        format!(
+            "https://{}/{}/{}#readme",
+            self.domain(),
+            self.owner,
+            self.repo
+        )
+    }
+
+    // Supports https, git, and ssh URLs, like hosted-git-info.
+    pub fn parse_url(url: &str) -> Option<Self> {
+        let url = url.strip_prefix("git+").unwrap_or(url);
+        let url = url.split('#').next().unwrap_or(url);
+        let url = ["https://", "http://", "git://", "ssh://"]
+            .into_iter()
+            .find_map(|scheme| url.strip_prefix(scheme))
+            .unwrap_or(url);
+        let url = url.strip_prefix("git@").unwrap_or(url);
+
+        let (domain, path) = url.split_once(['/', ':'])?;
+        let host = match domain {
+            "bitbucket.org" => "bitbucket",
+            "gist.github.com" => "gist",
+            "github.com" | "www.github.com" => "github",
+            "gitlab.com" => "gitlab",
+            _ => return None,
+        };
+
+        let (owner, repo) = path.trim_end_matches('/').split_once('/')?;
+        let repo = repo.strip_suffix(".git").unwrap_or(repo);
+
+        if owner.is_empty() || repo.is_empty() || repo.contains('/') {
+            return None;
+        }
+
+        Some(Self {
+            host: host.to_owned(),
+            owner: owner.to_owned(),
+            repo: repo.to_owned(),
+        })
+    }
+}
+
+impl RepositoryField {
+    pub fn to_hosted(&self) -> Option<HostedRepository> {
+        match self {
+            Self::Shorthand { host, owner, repo } => Some(HostedRepository {
+                host: host.to_owned(),
+                owner: owner.to_owned(),
+                repo: repo.to_owned(),
+            }),
+            Self::Url(url) | Self::Object { url, .. } => HostedRepository::parse_url(url),
+        }
+    }
+}
+
+// Like `path.join('/', bin).slice(1)` in npm, which
+// removes leading `./` and prevents escaping the root.
+fn secure_path(path: &Path) -> PathBuf {
+    let mut result = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::Normal(part) => result.push(part),
+            Component::ParentDir => {
+                result.pop();
+            }
+            _ => {}
+        };
+    }
+
+    result
+}
+
+pub(crate) fn walk_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(root.join(dir)) else {
+        return;
+    };
+
+    let mut entries = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+
+    entries.sort();
+
+    for path in entries {
+        let Some(name) = path.file_name() else {
+            continue;
+        };
+
+        // Dotfiles are ignored
+        if name.to_string_lossy().starts_with('.') {
+            continue;
+        }
+
+        if path.is_dir() {
+            walk_files(root, &dir.join(name), files);
+        } else {
+            files.push(dir.join(name));
+        }
+    }
+}
+
+fn is_man_page(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| {
+        ext.len() == 1 && ext.to_string_lossy().chars().all(|c| c.is_ascii_digit())
+    })
+}
+
+impl PackageJson {
+    // Applies the same normalization that npm does when installing or
+    // publishing a package. The `directories.bin` and `directories.man` files,
+    // and `binding.gyp` and `server.js` for default scripts, are read from `root`.
+    pub fn normalize(&mut self, root: &Path) {
+        self.normalize_bundle_dependencies();
+        self.normalize_bin(root);
+        self.normalize_man(root);
+        self.normalize_bugs();
+        self.normalize_repository();
+        self.normalize_scripts(root);
+    }
+
+    fn normalize_bundle_dependencies(&mut self) {
+        if self.bundle_dependencies.is_none() {
+            self.bundle_dependencies = self.bundled_dependencies.take();
+        }
+
+        self.bundle_dependencies = match self.bundle_dependencies.take() {
+            Some(BundleDependenciesField::Enabled(true)) => Some(BundleDependenciesField::List(
+                self.dependencies
+                    .as_ref()
+                    .map(|deps| deps.keys().cloned().collect())
+                    .unwrap_or_default(),
+            )),
+            Some(BundleDependenciesField::Enabled(false)) => None,
+            other => other,
+        };
+    }
+
+    fn normalize_bin(&mut self, root: &Path) {
+        let bin = match self.bin.take() {
+            Some(BinField::String(path)) => {
+                let Some(name) = &self.name else {
+                    return;
+                };
+
+                // Scoped packages use the name without the scope
+                let name = name.rsplit('/').next().unwrap_or(name);
+
+                FxIndexMap::from_iter([(name.to_owned(), path)])
+            }
+            Some(BinField::Map(map)) => map,
+            None => {
+                let Some(bin_dir) = self.directories.as_ref().and_then(|dirs| dirs.bin.as_ref())
+                else {
+                    return;
+                };
+
+                let mut files = vec![];
+                walk_files(root, &secure_path(bin_dir), &mut files);
+
+                files
+                    .into_iter()
+                    .filter_map(|file| {
+                        let name = file.file_name()?.to_string_lossy().into_owned();
+
+                        Some((name, file))
+                    })
+                    .collect()
+            }
+        };
+
+        let bin = bin
+            .into_iter()
+            .filter_map(|(name, path)| {
+                let name = secure_path(Path::new(&name))
+                    .file_name()?
+                    .to_string_lossy()
+                    .into_owned();
+                let path = secure_path(&path);
+
+                (!name.is_empty() && !path.as_os_str().is_empty()).then_some((name, path))
+            })
+            .collect::<FxIndexMap<_, _>>();
+
+        if !bin.is_empty() {
+            self.bin = Some(BinField::Map(bin));
+        }
+    }
+
+    fn normalize_man(&mut self, root: &Path) {
+        if self.man.is_some() {
+            return;
+        }
+
+        let Some(man_dir) = self.directories.as_ref().and_then(|dirs| dirs.man.as_ref()) else {
+            return;
+        };
+
+        let mut files = vec![];
+        walk_files(root, &secure_path(man_dir), &mut files);
+        files.retain(|file| is_man_page(file));
+
+        if !files.is_empty() {
+            self.man = Some(ManField::List(files));
+        }
+    }
+
+    fn normalize_bugs(&mut self) {
+        if let Some(BugsField::Url(url)) = &self.bugs {
+            self.bugs = Some(if url.contains('@') && !url.contains("://") {
+                BugsField::Object {
+                    url: None,
+                    email: Some(url.to_owned()),
+                }
+            } else {
+                BugsField::Object {
+                    url: Some(url.to_owned()),
+                    email: None,
+                }
+            });
+        }
+    }
+
+    fn normalize_repository(&mut self) {
+        let Some(repository) = self.repository.take() else {
+            return;
+        };
+
+        let hosted = repository.to_hosted();
+
+        self.repository = Some(match repository {
+            RepositoryField::Shorthand { .. } => RepositoryField::Object {
+                type_of: Some("git".into()),
+                url: hosted.as_ref().unwrap().git_url(),
+                directory: None,
+            },
+            RepositoryField::Url(url) => RepositoryField::Object {
+                type_of: Some("git".into()),
+                url,
+                directory: None,
+            },
+            object => object,
+        });
+
+        let Some(hosted) = hosted else {
+            return;
+        };
+
+        if self.bugs.is_none() {
+            self.bugs = Some(BugsField::Object {
+                url: Some(hosted.bugs_url()),
+                email: None,
+            });
+        }
+
+        if self.homepage.is_none() {
+            self.homepage = Some(hosted.homepage_url());
+        }
+    }
+
+    fn normalize_scripts(&mut self, root: &Path) {
+        let scripts = self.scripts.get_or_insert_with(Default::default);
+
+        // Native addons are built on install
+        if root.join("binding.gyp").exists()
+            && !scripts.contains_key("install")
+            && !scripts.contains_key("preinstall")
+        {
+            scripts.insert("install".into(), "node-gyp rebuild".into());
+        }
+
+        if root.join("server.js").exists() && !scripts.contains_key("start") {
+            scripts.insert("start".into(), "node server.js".into());
+        }
+
+        if scripts.is_empty() {
+            self.scripts = None;
+        }
+    }
+}
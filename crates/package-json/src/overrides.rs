@@ -0,0 +1,345 @@
+use crate::package_json::PackageJson;
+use crate::protocols::VersionProtocol;
+use crate::range::Range;
+use crate::FxIndexMap;
+use rustc_hash::FxHashMap;
+use semver::Version;
+use serde::Deserialize;
+use std::str::FromStr;
+
+// https://docs.npmjs.com/cli/v10/configuring-npm/package-json#overrides
+pub type OverridesMap = FxIndexMap<String, OverrideField>;
+
+// https://yarnpkg.com/configuration/manifest#resolutions
+pub type ResolutionsMap = FxIndexMap<String, String>;
+
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum OverrideField {
+    Version(String),
+    Nested(OverridesMap),
+}
+
+// https://pnpm.io/package_json#pnpmoverrides
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PnpmField {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overrides: Option<FxIndexMap<String, String>>,
+
+    #[serde(flatten)]
+    pub other_fields: FxHashMap<String, serde_json::Value>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum OverrideSource {
+    Npm,
+    Pnpm,
+    Yarn,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OverrideSelector {
+    // Zero or more packages, like `**` in yarn
+    AnyDepth,
+    Package { name: String, range: Option<Range> },
+}
+
+impl OverrideSelector {
+    fn matches(&self, segment: &str) -> bool {
+        let Self::Package { name, range } = self else {
+            return true;
+        };
+
+        let (segment_name, segment_version) = split_name(segment);
+
+        if name != segment_name {
+            return false;
+        }
+
+        match (range, segment_version) {
+            (None, _) => true,
+            (Some(range), Some(version)) => {
+                Version::parse(version).is_ok_and(|version| range.satisfies(&version))
+            }
+            (Some(range), None) => range.is_any(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum OverrideValue {
+    // $name, references the version of a direct dependency
+    Reference(String),
+    // -, removes the dependency (pnpm only)
+    Remove,
+    Version(VersionProtocol),
+}
+
+impl FromStr for OverrideValue {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        if value == "-" {
+            return Ok(Self::Remove);
+        }
+
+        if let Some(name) = value.strip_prefix('$') {
+            return Ok(Self::Reference(name.to_owned()));
+        }
+
+        VersionProtocol::from_str(value)
+            .map(Self::Version)
+            .map_err(|error| error.to_string())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OverrideRule {
+    // Original key (or keys joined with `>` when nested).
+    pub key: String,
+
+    // Selectors from the root to the target package (the last).
+    pub selectors: Vec<OverrideSelector>,
+
+    pub source: OverrideSource,
+    pub value: OverrideValue,
+}
+
+impl OverrideRule {
+    // The path is a list of package names (or `name@version`), starting
+    // from a direct dependency of the root package, to the target package.
+    pub fn matches(&self, path: &[&str]) -> bool {
+        matches_selectors(&self.selectors, path)
+    }
+
+    // Explicit packages and ranges are more specific than wildcards.
+    fn specificity(&self) -> (usize, usize) {
+        self.selectors
+            .iter()
+            .fold((0, 0), |(packages, ranges), selector| match selector {
+                OverrideSelector::Package { range, .. } => {
+                    (packages + 1, ranges + usize::from(range.is_some()))
+                }
+                OverrideSelector::AnyDepth => (packages, ranges),
+            })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct OverrideMatch<'a> {
+    pub rule: &'a OverrideRule,
+
+    // Version forced by the rule, with references resolved.
+    // Is `None` if the dependency is removed, or the reference is unknown.
+    pub version: Option<VersionProtocol>,
+}
+
+fn matches_selectors(selectors: &[OverrideSelector], path: &[&str]) -> bool {
+    match selectors.split_first() {
+        None => path.is_empty(),
+        Some((OverrideSelector::AnyDepth, rest)) => {
+            (0..=path.len()).any(|skip| matches_selectors(rest, &path[skip..]))
+        }
+        Some((selector, rest)) => path.split_first().is_some_and(|(segment, path)| {
+            selector.matches(segment) && matches_selectors(rest, path)
+        }),
+    }
+}
+
+// Returns the name and version/range of a `name@range` descriptor.
+fn split_name(value: &str) -> (&str, Option<&str>) {
+    // Skip the leading @ of scoped packages
+    match value.char_indices().skip(1).find(|(_, c)| *c == '@') {
+        Some((at, _)) => (&value[..at], Some(&value[at + 1..])),
+        None => (value, None),
+    }
+}
+
+fn create_package_selector(value: &str) -> Option<OverrideSelector> {
+    let (name, range) = split_name(value.trim());
+
+    if name.is_empty() {
+        return None;
+    }
+
+    Some(OverrideSelector::Package {
+        name: name.to_owned(),
+        range: match range {
+            // Yarn descriptors include the protocol
+            Some(range) => Some(Range::parse(range.trim_start_matches("npm:")).ok()?),
+            None => None,
+        },
+    })
+}
+
+fn collect_npm_rules(
+    map: &OverridesMap,
+    parents: &[OverrideSelector],
+    parent_keys: &[&str],
+    rules: &mut Vec<OverrideRule>,
+) {
+    for (key, field) in map {
+        // Handled by the parent
+        if key == "." {
+            continue;
+        }
+
+        let Some(selector) = create_package_selector(key) else {
+            continue;
+        };
+
+        // Nested overrides apply anywhere within the parent's tree
+        let mut selectors = parents.to_vec();
+        selectors.push(OverrideSelector::AnyDepth);
+        selectors.push(selector);
+
+        let mut keys = parent_keys.to_vec();
+        keys.push(key);
+
+        match field {
+            OverrideField::Version(value) => {
+                if let Ok(value) = OverrideValue::from_str(value) {
+                    rules.push(OverrideRule {
+                        key: keys.join(" > "),
+                        selectors,
+                        source: OverrideSource::Npm,
+                        value,
+                    });
+                }
+            }
+            OverrideField::Nested(nested) => {
+                // `.` overrides the parent package itself
+                if let Some(OverrideField::Version(value)) = nested.get(".") {
+                    if let Ok(value) = OverrideValue::from_str(value) {
+                        rules.push(OverrideRule {
+                            key: keys.join(" > "),
+                            selectors: selectors.clone(),
+                            source: OverrideSource::Npm,
+                            value,
+                        });
+                    }
+                }
+
+                collect_npm_rules(nested, &selectors, &keys, rules);
+            }
+        }
+    }
+}
+
+fn create_yarn_rule(key: &str, value: &str) -> Option<OverrideRule> {
+    let mut selectors = vec![];
+    let mut parts = key.split('/').peekable();
+
+    // A single package applies at any depth
+    if !key.contains("**") && split_package_count(key) == 1 {
+        selectors.push(OverrideSelector::AnyDepth);
+    }
+
+    while let Some(part) = parts.next() {
+        if part == "**" {
+            selectors.push(OverrideSelector::AnyDepth);
+        } else if part.starts_with('@') {
+            selectors.push(create_package_selector(&format!(
+                "{part}/{}",
+                parts.next()?
+            ))?);
+        } else {
+            selectors.push(create_package_selector(part)?);
+        }
+    }
+
+    Some(OverrideRule {
+        key: key.to_owned(),
+        selectors,
+        source: OverrideSource::Yarn,
+        value: OverrideValue::from_str(value).ok()?,
+    })
+}
+
+fn split_package_count(key: &str) -> usize {
+    let slashes = key.matches('/').count();
+    let scopes = key.split('/').filter(|part| part.starts_with('@')).count();
+
+    slashes + 1 - scopes
+}
+
+fn create_pnpm_rule(key: &str, value: &str) -> Option<OverrideRule> {
+    let mut selectors = vec![OverrideSelector::AnyDepth];
+
+    // The child must be a direct dependency of the parent
+    for part in key.split('>') {
+        selectors.push(create_package_selector(part)?);
+    }
+
+    Some(OverrideRule {
+        key: key.to_owned(),
+        selectors,
+        source: OverrideSource::Pnpm,
+        value: OverrideValue::from_str(value).ok()?,
+    })
+}
+
+impl PackageJson {
+    // Returns all override rules from `overrides` (npm), `resolutions` (yarn),
+    // and `pnpm.overrides` (pnpm). Invalid rules are skipped.
+    pub fn get_override_rules(&self) -> Vec<OverrideRule> {
+        let mut rules = vec![];
+
+        if let Some(overrides) = &self.overrides {
+            collect_npm_rules(overrides, &[], &[], &mut rules);
+        }
+
+        if let Some(resolutions) = &self.resolutions {
+            rules.extend(
+                resolutions
+                    .iter()
+                    .filter_map(|(key, value)| create_yarn_rule(key, value)),
+            );
+        }
+
+        if let Some(overrides) = self.pnpm.as_ref().and_then(|pnpm| pnpm.overrides.as_ref()) {
+            rules.extend(
+                overrides
+                    .iter()
+                    .filter_map(|(key, value)| create_pnpm_rule(key, value)),
+            );
+        }
+
+        rules
+    }
+
+    // Finds the most specific override that applies to the last package
+    // in the path. If multiple are equally specific, the last one wins.
+    pub fn find_override<'a>(
+        &self,
+        rules: &'a [OverrideRule],
+        path: &[&str],
+    ) -> Option<OverrideMatch<'a>> {
+        let rule = rules
+            .iter()
+            .filter(|rule| rule.matches(path))
+            .max_by_key(|rule| rule.specificity())?;
+
+        let version = match &rule.value {
+            OverrideValue::Reference(name) => [
+                &self.dependencies,
+                &self.dev_dependencies,
+                &self.optional_dependencies,
+                &self.peer_dependencies,
+            ]
+            .into_iter()
+            .flatten()
+            .find_map(|deps| deps.get(name))
+            .cloned(),
+            OverrideValue::Remove => None,
+            OverrideValue::Version(version) => Some(version.to_owned()),
+        };
+
+        Some(OverrideMatch { rule, version })
+    }
+}
@@ -1,4 +1,10 @@
+use crate::dev_engines::DevEnginesField;
+use crate::metadata_fields::*;
+use crate::overrides::*;
 use crate::protocols::VersionProtocol;
+use crate::publish_config::PublishConfigField;
+use crate::side_effects::SideEffectsField;
+use crate::types_resolver::TypesVersionsMap;
 use crate::{import_export::*, FxIndexMap};
 use rustc_hash::FxHashMap;
 use semver::Version;
@@ -19,6 +25,7 @@ pub type ScriptsMap = FxIndexMap<String, String>;
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PackageJson {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -38,6 +45,70 @@ pub struct PackageJson {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub scripts: Option<ScriptsMap>,
 
+    // Metadata
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub private: Option<bool>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub license: Option<LicenseField>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub keywords: Option<Vec<String>>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub homepage: Option<String>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bugs: Option<BugsField>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub repository: Option<RepositoryField>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub author: Option<Person>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient_list",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub contributors: Option<Vec<Person>>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub funding: Option<FundingField>,
+
     // Entry points
     #[serde(skip_serializing_if = "Option::is_none")]
     pub main: Option<PathBuf>,
@@ -54,6 +125,62 @@ pub struct PackageJson {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub exports: Option<ImportExportField>,
 
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub types: Option<PathBuf>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub typings: Option<PathBuf>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub types_versions: Option<TypesVersionsMap>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bin: Option<BinField>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub files: Option<Vec<String>>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub directories: Option<DirectoriesField>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub man: Option<ManField>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub side_effects: Option<SideEffectsField>,
+
     // Dependencies
     #[serde(skip_serializing_if = "Option::is_none")]
     pub dependencies: Option<DependenciesMap<VersionProtocol>>,
@@ -70,8 +197,20 @@ pub struct PackageJson {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub peer_dependencies_meta: Option<DependenciesMap<PeerDependencyMetaField>>,
 
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub bundle_dependencies: Option<Vec<String>>,
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bundle_dependencies: Option<BundleDependenciesField>,
+
+    // Alternate spelling, merged into `bundle_dependencies` when normalized.
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bundled_dependencies: Option<BundleDependenciesField>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub optional_dependencies: Option<DependenciesMap<VersionProtocol>>,
@@ -80,9 +219,48 @@ pub struct PackageJson {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub engines: Option<EnginesMap>,
 
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub dev_engines: Option<DevEnginesField>,
+
+    // Kept as the raw value, as package managers accept values that can't
+    // be parsed, like tags. Use `parse_package_manager` for a typed value.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub package_manager: Option<String>,
 
+    // Overrides
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub overrides: Option<OverridesMap>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub resolutions: Option<ResolutionsMap>,
+
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub pnpm: Option<PnpmField>,
+
+    // Publishing
+    #[serde(
+        default,
+        deserialize_with = "lenient",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub publish_config: Option<PublishConfigField>,
+
     // Workspaces
     #[serde(skip_serializing_if = "Option::is_none")]
     pub workspaces: Option<WorkspacesField>,
@@ -95,6 +273,7 @@ pub struct PackageJson {
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(untagged)]
 pub enum BrowserField {
     String(String),
@@ -103,6 +282,7 @@ pub enum BrowserField {
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct DependencyMetaField {
     // pnpm - https://pnpm.io/package_json#dependenciesmeta
@@ -122,6 +302,7 @@ pub struct DependencyMetaField {
 
 #[derive(Clone, Debug, Default, Deserialize, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PeerDependencyMetaField {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -130,6 +311,7 @@ pub struct PeerDependencyMetaField {
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(untagged, rename_all = "camelCase")]
 pub enum WorkspacesField {
     Globs(Vec<String>),
@@ -140,3 +322,22 @@ pub enum WorkspacesField {
         nohoist: Option<Vec<String>>,
     },
 }
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DependencyType {
+    Development,
+    Production,
+    Peer,
+    Optional,
+}
+
+impl DependencyType {
+    pub fn field_name(&self) -> &'static str {
+        match self {
+            DependencyType::Development => "devDependencies",
+            DependencyType::Production => "dependencies",
+            DependencyType::Peer => "peerDependencies",
+            DependencyType::Optional => "optionalDependencies",
+        }
+    }
+}
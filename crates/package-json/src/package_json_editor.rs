@@ -0,0 +1,439 @@
+use crate::json_spans::{escape_pointer_token, index_json_spans, JsonSpan};
+use crate::json_value::{JsonMap, JsonValue};
+use crate::package_json::{DependencyType, PackageJson};
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use serde_json::ser::PrettyFormatter;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "miette", derive(miette::Diagnostic))]
+pub enum PackageJsonEditorError {
+    #[error("Expected an object at the root of package.json.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::editor::invalid_root))
+    )]
+    InvalidRoot,
+
+    #[error("Field `{0}` is not an object.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::editor::not_an_object))
+    )]
+    NotAnObject(String),
+
+    #[error("Editor was not loaded from a file, unable to save.")]
+    #[cfg_attr(feature = "miette", diagnostic(code(package_json::editor::no_path)))]
+    NoPath,
+
+    #[error("Failed to read or write {}.", .path.display())]
+    #[cfg_attr(feature = "miette", diagnostic(code(package_json::editor::io)))]
+    Io {
+        path: PathBuf,
+        #[source]
+        error: std::io::Error,
+    },
+
+    #[error(transparent)]
+    #[cfg_attr(feature = "miette", diagnostic(code(package_json::editor::json)))]
+    Json(#[from] serde_json::Error),
+}
+
+// Applies mutations to the raw JSON of a `package.json`, while
+// preserving the key order, indentation, and line endings. Values that
+// were not changed are written with their original text.
+#[derive(Clone, Debug)]
+pub struct PackageJsonEditor {
+    pub path: Option<PathBuf>,
+
+    data: JsonMap,
+    eol: &'static str,
+    indent: String,
+    original: JsonMap,
+    source: String,
+    trailing_newline: bool,
+}
+
+impl PackageJsonEditor {
+    pub fn new<T: AsRef<str>>(content: T) -> Result<Self, PackageJsonEditorError> {
+        let content = content.as_ref();
+
+        let JsonValue::Object(data) = serde_json::from_str(content)? else {
+            return Err(PackageJsonEditorError::InvalidRoot);
+        };
+
+        Ok(Self {
+            path: None,
+            original: data.clone(),
+            data,
+            eol: if content.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            },
+            indent: detect_indent(content),
+            source: content.to_owned(),
+            trailing_newline: content.ends_with('\n'),
+        })
+    }
+
+    pub fn load<T: AsRef<Path>>(path: T) -> Result<Self, PackageJsonEditorError> {
+        let path = path.as_ref();
+        let content = fs::read_to_string(path).map_err(|error| PackageJsonEditorError::Io {
+            path: path.to_owned(),
+            error,
+        })?;
+
+        let mut editor = Self::new(content)?;
+        editor.path = Some(path.to_owned());
+
+        Ok(editor)
+    }
+
+    pub fn get_field(&self, key: &str) -> Option<&JsonValue> {
+        self.data.get(key)
+    }
+
+    pub fn set_field<T: Serialize>(
+        &mut self,
+        key: &str,
+        value: T,
+    ) -> Result<(), PackageJsonEditorError> {
+        let value = JsonValue::from_serialize(&value)?;
+
+        // Replacing an existing key keeps its position
+        self.data.insert(key.to_owned(), value);
+
+        Ok(())
+    }
+
+    pub fn remove_field(&mut self, key: &str) -> Option<JsonValue> {
+        self.data.shift_remove(key)
+    }
+
+    pub fn set_version<T: fmt::Display>(&mut self, version: T) {
+        self.data
+            .insert("version".into(), JsonValue::String(version.to_string()));
+    }
+
+    pub fn set_script<T: AsRef<str>>(
+        &mut self,
+        name: &str,
+        command: T,
+    ) -> Result<(), PackageJsonEditorError> {
+        self.get_object_mut("scripts")?.insert(
+            name.to_owned(),
+            JsonValue::String(command.as_ref().to_owned()),
+        );
+
+        Ok(())
+    }
+
+    // If the section is sorted, the dependency will be inserted
+    // in sorted order, otherwise it's appended to the end.
+    pub fn add_dependency<T: fmt::Display>(
+        &mut self,
+        dep_type: DependencyType,
+        name: &str,
+        version: T,
+    ) -> Result<(), PackageJsonEditorError> {
+        let deps = self.get_object_mut(dep_type.field_name())?;
+        let version = JsonValue::String(version.to_string());
+
+        if deps.contains_key(name) {
+            deps.insert(name.to_owned(), version);
+        } else if is_sorted(deps) {
+            let index = deps.keys().take_while(|key| key.as_str() < name).count();
+
+            deps.shift_insert(index, name.to_owned(), version);
+        } else {
+            deps.insert(name.to_owned(), version);
+        }
+
+        Ok(())
+    }
+
+    // Returns true if the dependency was removed. If the section
+    // becomes empty, it will also be removed.
+    pub fn remove_dependency(&mut self, dep_type: DependencyType, name: &str) -> bool {
+        let field = dep_type.field_name();
+
+        let Some(JsonValue::Object(deps)) = self.data.get_mut(field) else {
+            return false;
+        };
+
+        if deps.shift_remove(name).is_none() {
+            return false;
+        }
+
+        if deps.is_empty() {
+            self.data.shift_remove(field);
+        }
+
+        true
+    }
+
+    // Returns true if the dependency was renamed. The version is
+    // preserved, and the position is kept, unless the section is sorted.
+    pub fn rename_dependency(
+        &mut self,
+        dep_type: DependencyType,
+        old_name: &str,
+        new_name: &str,
+    ) -> bool {
+        let Some(JsonValue::Object(deps)) = self.data.get_mut(dep_type.field_name()) else {
+            return false;
+        };
+
+        if !deps.contains_key(old_name) {
+            return false;
+        }
+
+        let sorted = is_sorted(deps);
+
+        // Avoid a duplicate key if the new name already exists,
+        // and find the position after it has been removed
+        if old_name != new_name {
+            deps.shift_remove(new_name);
+        }
+
+        let (index, _, version) = deps.shift_remove_full(old_name).unwrap();
+
+        let index = if sorted {
+            deps.keys()
+                .take_while(|key| key.as_str() < new_name)
+                .count()
+        } else {
+            index
+        };
+
+        deps.shift_insert(index, new_name.to_owned(), version);
+
+        true
+    }
+
+    pub fn to_manifest(&self) -> Result<PackageJson, PackageJsonEditorError> {
+        Ok(JsonValue::Object(self.data.clone()).to_deserialize()?)
+    }
+
+    pub fn save(&self) -> Result<(), PackageJsonEditorError> {
+        match &self.path {
+            Some(path) => self.save_to(path),
+            None => Err(PackageJsonEditorError::NoPath),
+        }
+    }
+
+    pub fn save_to<T: AsRef<Path>>(&self, path: T) -> Result<(), PackageJsonEditorError> {
+        let path = path.as_ref();
+
+        fs::write(path, self.to_string()).map_err(|error| PackageJsonEditorError::Io {
+            path: path.to_owned(),
+            error,
+        })
+    }
+
+    fn get_object_mut(&mut self, key: &str) -> Result<&mut JsonMap, PackageJsonEditorError> {
+        match self
+            .data
+            .entry(key.to_owned())
+            .or_insert_with(|| JsonValue::Object(JsonMap::new()))
+        {
+            JsonValue::Object(map) => Ok(map),
+            _ => Err(PackageJsonEditorError::NotAnObject(key.to_owned())),
+        }
+    }
+}
+
+impl fmt::Display for PackageJsonEditor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.data == self.original {
+            return write!(f, "{}", self.source);
+        }
+
+        let printer = JsonPrinter {
+            eol: self.eol,
+            indent: &self.indent,
+            source: &self.source,
+            spans: index_json_spans(&self.source),
+        };
+
+        let mut output = String::new();
+        let data = JsonValue::Object(self.data.clone());
+        let original = JsonValue::Object(self.original.clone());
+
+        printer
+            .write_value(&mut output, &data, Some(&original), "", 0)
+            .map_err(|_| fmt::Error)?;
+
+        if self.trailing_newline {
+            output.push_str(self.eol);
+        }
+
+        write!(f, "{output}")
+    }
+}
+
+// Writes values that are equal to the original with their source text,
+// so that formatting like inline arrays is kept. Changed objects and arrays
+// keep their original layout, while new values are pretty printed.
+struct JsonPrinter<'a> {
+    eol: &'a str,
+    indent: &'a str,
+    source: &'a str,
+    spans: FxHashMap<String, JsonSpan>,
+}
+
+impl JsonPrinter<'_> {
+    fn write_value(
+        &self,
+        output: &mut String,
+        value: &JsonValue,
+        original: Option<&JsonValue>,
+        pointer: &str,
+        depth: usize,
+    ) -> Result<(), serde_json::Error> {
+        let source = self
+            .spans
+            .get(pointer)
+            .map(|(offset, length)| &self.source[*offset..offset + length]);
+
+        let (Some(original), Some(source)) = (original, source) else {
+            return self.write_new_value(output, value, depth);
+        };
+
+        if value == original {
+            output.push_str(source);
+
+            return Ok(());
+        }
+
+        let entries = match (value, original) {
+            (JsonValue::Object(map), JsonValue::Object(original_map)) => map
+                .iter()
+                .map(|(key, item)| {
+                    (
+                        Some(key.as_str()),
+                        item,
+                        original_map.get(key),
+                        format!("{pointer}/{}", escape_pointer_token(key)),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            (JsonValue::Array(items), JsonValue::Array(original_items)) => items
+                .iter()
+                .enumerate()
+                .map(|(index, item)| {
+                    (
+                        None,
+                        item,
+                        original_items.get(index),
+                        format!("{pointer}/{index}"),
+                    )
+                })
+                .collect::<Vec<_>>(),
+            _ => return self.write_new_value(output, value, depth),
+        };
+
+        let (open, close) = if matches!(value, JsonValue::Object(_)) {
+            ("{", "}")
+        } else {
+            ("[", "]")
+        };
+
+        if entries.is_empty() {
+            output.push_str(open);
+            output.push_str(close);
+
+            return Ok(());
+        }
+
+        // Containers on a single line stay on a single line
+        let inline = !source.contains('\n');
+        let padding = if inline && source[1..].starts_with(' ') {
+            " "
+        } else {
+            ""
+        };
+
+        output.push_str(open);
+
+        for (index, (key, item, original_item, item_pointer)) in entries.iter().enumerate() {
+            if index > 0 {
+                output.push(',');
+            }
+
+            if inline {
+                output.push_str(if index > 0 { " " } else { padding });
+            } else {
+                output.push_str(self.eol);
+                output.push_str(&self.indent.repeat(depth + 1));
+            }
+
+            if let Some(key) = key {
+                output.push_str(&serde_json::to_string(key)?);
+                output.push_str(": ");
+            }
+
+            if inline {
+                output.push_str(&serde_json::to_string(item)?);
+            } else {
+                self.write_value(output, item, *original_item, item_pointer, depth + 1)?;
+            }
+        }
+
+        if inline {
+            output.push_str(padding);
+        } else {
+            output.push_str(self.eol);
+            output.push_str(&self.indent.repeat(depth));
+        }
+
+        output.push_str(close);
+
+        Ok(())
+    }
+
+    fn write_new_value(
+        &self,
+        output: &mut String,
+        value: &JsonValue,
+        depth: usize,
+    ) -> Result<(), serde_json::Error> {
+        let mut buffer = vec![];
+        let mut serializer = serde_json::Serializer::with_formatter(
+            &mut buffer,
+            PrettyFormatter::with_indent(self.indent.as_bytes()),
+        );
+
+        value.serialize(&mut serializer)?;
+
+        let json = String::from_utf8_lossy(&buffer);
+        let newline = format!("{}{}", self.eol, self.indent.repeat(depth));
+
+        output.push_str(&json.replace('\n', &newline));
+
+        Ok(())
+    }
+}
+
+// Use the whitespace of the first indented line, like detect-indent.
+fn detect_indent(content: &str) -> String {
+    content
+        .lines()
+        .skip(1)
+        .map(|line| {
+            line.chars()
+                .take_while(|c| *c == ' ' || *c == '\t')
+                .collect::<String>()
+        })
+        .find(|indent| !indent.is_empty())
+        .unwrap_or_else(|| "  ".into())
+}
+
+fn is_sorted(map: &JsonMap) -> bool {
+    map.keys().zip(map.keys().skip(1)).all(|(a, b)| a <= b)
+}
@@ -0,0 +1,353 @@
+// Based on npm's `npm-packlist`.
+// https://github.com/npm/npm-packlist
+
+use crate::metadata_fields::BinField;
+use crate::package_json::PackageJson;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "miette", derive(miette::Diagnostic))]
+pub enum PackListError {
+    #[error("Failed to parse ignore rules in {}.", .path.display())]
+    #[cfg_attr(feature = "miette", diagnostic(code(package_json::packlist::ignore)))]
+    Ignore {
+        path: PathBuf,
+        #[source]
+        error: ignore::Error,
+    },
+
+    #[error("Failed to read {}.", .path.display())]
+    #[cfg_attr(feature = "miette", diagnostic(code(package_json::packlist::io)))]
+    Io {
+        path: PathBuf,
+        #[source]
+        error: std::io::Error,
+    },
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PackList {
+    // Sorted file paths, relative to the package root.
+    pub files: Vec<PathBuf>,
+
+    // Total size of all files in bytes, before compression.
+    pub size: u64,
+}
+
+// Excluded at any depth, regardless of `files` or ignore files.
+const ALWAYS_EXCLUDED: &[&str] = &[
+    ".DS_Store",
+    ".git",
+    ".gitignore",
+    ".hg",
+    ".lock-wscript",
+    ".npmignore",
+    ".npmrc",
+    ".svn",
+    "CVS",
+    "config.gypi",
+    "node_modules",
+    "npm-debug.log",
+];
+
+// Excluded only from the package root.
+const ROOT_EXCLUDED: &[&str] = &[
+    "bun.lockb",
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+];
+
+// Included from the package root, case-insensitive, by prefix. For example,
+// `LICENSE-MIT` and `README.zh.md` are included.
+const ROOT_INCLUDED: &[&str] = &["copying", "licence", "license", "readme"];
+
+fn is_always_excluded(name: &str, is_root: bool) -> bool {
+    ALWAYS_EXCLUDED.contains(&name)
+        || (is_root && ROOT_EXCLUDED.contains(&name))
+        || name.starts_with("._")
+        || name.ends_with(".orig")
+        || (name.starts_with('.') && name.ends_with(".swp"))
+        || name.starts_with(".wafpickle-")
+}
+
+fn is_always_included(name: &str) -> bool {
+    let name = name.to_lowercase();
+
+    name == "package.json" || ROOT_INCLUDED.iter().any(|prefix| name.starts_with(prefix))
+}
+
+fn normalize_path(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| matches!(component, std::path::Component::Normal(_)))
+        .collect()
+}
+
+struct PackListWalker<'a> {
+    files: Option<Gitignore>,
+    file_dirs: Option<Gitignore>,
+    list: BTreeSet<PathBuf>,
+    root: &'a Path,
+}
+
+impl<'a> PackListWalker<'a> {
+    // Patterns are relative to the root, unless they start with `**/`.
+    fn normalize_pattern(pattern: &str) -> Option<(bool, String)> {
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(pattern) => (true, pattern),
+            None => (false, pattern),
+        };
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+
+        if pattern.is_empty() {
+            return None;
+        }
+
+        Some((
+            negated,
+            if pattern.starts_with('/') || pattern.starts_with("**/") {
+                pattern.to_owned()
+            } else {
+                format!("/{pattern}")
+            },
+        ))
+    }
+
+    fn build_ignore(root: &Path, lines: &[String]) -> Result<Gitignore, PackListError> {
+        let to_error = |error| PackListError::Ignore {
+            path: root.join("package.json"),
+            error,
+        };
+        let mut builder = GitignoreBuilder::new(root);
+
+        for line in lines {
+            builder.add_line(None, line).map_err(to_error)?;
+        }
+
+        builder.build().map_err(to_error)
+    }
+
+    // Matches files included by the `files` field, including directory contents.
+    fn load_files(root: &Path, patterns: &[String]) -> Result<Gitignore, PackListError> {
+        let mut lines = vec![];
+
+        for (negated, pattern) in patterns
+            .iter()
+            .filter_map(|pattern| Self::normalize_pattern(pattern))
+        {
+            let negated = if negated { "!" } else { "" };
+
+            lines.push(format!("{negated}{pattern}"));
+            lines.push(format!("{negated}{pattern}/**"));
+        }
+
+        Self::build_ignore(root, &lines)
+    }
+
+    // Matches the parent directories of each `files` pattern, so that the walker
+    // can skip directories that can't contain included files. For example,
+    // `dist/esm/*.js` matches `dist` and `dist/esm`.
+    fn load_file_dirs(root: &Path, patterns: &[String]) -> Result<Gitignore, PackListError> {
+        let mut lines = vec![];
+
+        for (_, pattern) in patterns
+            .iter()
+            .filter_map(|pattern| Self::normalize_pattern(pattern))
+            .filter(|(negated, _)| !negated)
+        {
+            let segments = pattern
+                .trim_start_matches('/')
+                .split('/')
+                .collect::<Vec<_>>();
+
+            for index in 0..segments.len() - 1 {
+                // Any directory below a `**` segment may match
+                if segments[index] == "**" {
+                    lines.push(format!("/{}", segments[..=index].join("/")));
+                    break;
+                }
+
+                lines.push(format!("/{}/", segments[..=index].join("/")));
+            }
+        }
+
+        Self::build_ignore(root, &lines)
+    }
+
+    // `.npmignore` takes precedence over `.gitignore` in each directory.
+    fn load_ignore(dir: &Path) -> Result<Option<Gitignore>, PackListError> {
+        for name in [".npmignore", ".gitignore"] {
+            let path = dir.join(name);
+
+            if !path.is_file() {
+                continue;
+            }
+
+            let mut builder = GitignoreBuilder::new(dir);
+
+            if let Some(error) = builder.add(&path) {
+                return Err(PackListError::Ignore { path, error });
+            }
+
+            return builder
+                .build()
+                .map(Some)
+                .map_err(|error| PackListError::Ignore { path, error });
+        }
+
+        Ok(None)
+    }
+
+    fn is_ignored(ignores: &[Gitignore], path: &Path, is_dir: bool) -> bool {
+        // The closest ignore file wins
+        for ignore in ignores.iter().rev() {
+            match ignore.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            };
+        }
+
+        false
+    }
+
+    fn walk(&mut self, dir: &Path, mut ignores: Vec<Gitignore>) -> Result<(), PackListError> {
+        let abs_dir = self.root.join(dir);
+        let is_root = dir.as_os_str().is_empty();
+
+        // The `files` field replaces the root ignore file
+        if !is_root || self.files.is_none() {
+            if let Some(ignore) = Self::load_ignore(&abs_dir)? {
+                ignores.push(ignore);
+            }
+        }
+
+        let mut entries = fs::read_dir(&abs_dir)
+            .map_err(|error| PackListError::Io {
+                path: abs_dir.clone(),
+                error,
+            })?
+            .filter_map(|entry| entry.ok())
+            .collect::<Vec<_>>();
+
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+
+            if is_always_excluded(&name, is_root) {
+                continue;
+            }
+
+            let path = dir.join(name.as_ref());
+            let abs_path = abs_dir.join(name.as_ref());
+            let is_dir = abs_path.is_dir();
+
+            if Self::is_ignored(&ignores, &abs_path, is_dir) {
+                continue;
+            }
+
+            if is_dir {
+                if self.can_contain_files(&abs_path) {
+                    self.walk(&path, ignores.clone())?;
+                }
+            } else if self.files.as_ref().map_or(true, |files| {
+                files
+                    .matched_path_or_any_parents(&abs_path, false)
+                    .is_ignore()
+            }) {
+                self.list.insert(path);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn can_contain_files(&self, dir: &Path) -> bool {
+        match (&self.files, &self.file_dirs) {
+            (Some(files), Some(file_dirs)) => {
+                files.matched_path_or_any_parents(dir, true).is_ignore()
+                    || file_dirs.matched(dir, true).is_ignore()
+            }
+            _ => true,
+        }
+    }
+
+    fn include(&mut self, path: &Path) {
+        let path = normalize_path(path);
+
+        if !path.as_os_str().is_empty() && self.root.join(&path).is_file() {
+            self.list.insert(path);
+        }
+    }
+}
+
+impl PackageJson {
+    // Returns the files that would be included in the tarball created by
+    // `npm pack` or `npm publish`, walked from and relative to `root`.
+    pub fn get_pack_list(&self, root: &Path) -> Result<PackList, PackListError> {
+        let mut walker = PackListWalker {
+            files: match &self.files {
+                Some(files) => Some(PackListWalker::load_files(root, files)?),
+                None => None,
+            },
+            file_dirs: match &self.files {
+                Some(files) => Some(PackListWalker::load_file_dirs(root, files)?),
+                None => None,
+            },
+            list: BTreeSet::default(),
+            root,
+        };
+
+        walker.walk(Path::new(""), vec![])?;
+
+        for entry in fs::read_dir(root)
+            .map_err(|error| PackListError::Io {
+                path: root.to_path_buf(),
+                error,
+            })?
+            .filter_map(|entry| entry.ok())
+        {
+            let name = entry.file_name();
+
+            if is_always_included(&name.to_string_lossy()) {
+                walker.include(Path::new(&name));
+            }
+        }
+
+        if let Some(main) = &self.main {
+            walker.include(main);
+        }
+
+        match &self.bin {
+            Some(BinField::String(path)) => walker.include(path),
+            Some(BinField::Map(map)) => {
+                for path in map.values() {
+                    walker.include(path);
+                }
+            }
+            None => {}
+        };
+
+        let mut size = 0;
+
+        for file in &walker.list {
+            let path = root.join(file);
+
+            size += fs::metadata(&path)
+                .map_err(|error| PackListError::Io { path, error })?
+                .len();
+        }
+
+        Ok(PackList {
+            files: walker.list.into_iter().collect(),
+            size,
+        })
+    }
+}
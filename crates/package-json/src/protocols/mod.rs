@@ -1,15 +1,24 @@
+#[cfg(feature = "protocols")]
+mod package_manager;
+
 #[cfg(feature = "protocols")]
 mod version;
 
 #[cfg(feature = "protocols")]
 mod workspace;
 
+#[cfg(feature = "protocols")]
+pub use package_manager::*;
+
 #[cfg(feature = "protocols")]
 pub use version::*;
 
 #[cfg(feature = "protocols")]
 pub use workspace::*;
 
+#[cfg(not(feature = "protocols"))]
+pub type PackageManagerField = String;
+
 #[cfg(not(feature = "protocols"))]
 pub type VersionProtocol = String;
 
@@ -0,0 +1,155 @@
+use crate::package_json::PackageJson;
+use nodejs_package_managers::PackageManager;
+use semver::Version;
+use serde::Deserialize;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "miette", derive(miette::Diagnostic))]
+pub enum PackageManagerFieldError {
+    #[error("Invalid package manager hash `{0}`, expected `<algorithm>.<digest>`.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::package_manager::invalid_hash))
+    )]
+    InvalidHash(String),
+
+    #[error("Package manager `{0}` requires an exact version, for example `{0}@1.2.3`.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::package_manager::missing_version))
+    )]
+    MissingVersion(String),
+
+    #[error("Unknown package manager `{0}`. Supports bun, npm, pnpm, and yarn.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::package_manager::unknown))
+    )]
+    UnknownManager(String),
+
+    #[error("Failed to parse version: {0}")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::package_manager::invalid_version))
+    )]
+    Semver(#[from] semver::Error),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PackageManagerHash {
+    pub algorithm: String,
+    pub digest: String,
+}
+
+// https://nodejs.org/api/packages.html#packagemanager
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[serde(try_from = "String", into = "String")]
+pub struct PackageManagerField {
+    pub manager: PackageManager,
+    pub version: Version,
+    pub hash: Option<PackageManagerHash>,
+}
+
+impl PackageManagerField {
+    pub fn name(&self) -> &'static str {
+        match self.manager {
+            PackageManager::Bun => "bun",
+            PackageManager::Npm => "npm",
+            PackageManager::Pnpm => "pnpm",
+            PackageManager::Yarn | PackageManager::YarnLegacy => "yarn",
+        }
+    }
+}
+
+// Yarn v1 and v2+ are different package managers.
+pub fn parse_package_manager(
+    name: &str,
+    version: &Version,
+) -> Result<PackageManager, PackageManagerFieldError> {
+    Ok(match name {
+        "bun" => PackageManager::Bun,
+        "npm" => PackageManager::Npm,
+        "pnpm" => PackageManager::Pnpm,
+        "yarn" if version.major < 2 => PackageManager::YarnLegacy,
+        "yarn" => PackageManager::Yarn,
+        _ => return Err(PackageManagerFieldError::UnknownManager(name.to_owned())),
+    })
+}
+
+impl FromStr for PackageManagerField {
+    type Err = PackageManagerFieldError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let Some((name, version)) = value.split_once('@') else {
+            return Err(PackageManagerFieldError::MissingVersion(value.to_owned()));
+        };
+
+        // The hash is separated with a `+`, like build metadata
+        let (version, hash) = match version.split_once('+') {
+            Some((version, hash)) => {
+                let Some((algorithm, digest)) = hash.split_once('.') else {
+                    return Err(PackageManagerFieldError::InvalidHash(hash.to_owned()));
+                };
+
+                (
+                    version,
+                    Some(PackageManagerHash {
+                        algorithm: algorithm.to_owned(),
+                        digest: digest.to_owned(),
+                    }),
+                )
+            }
+            None => (version, None),
+        };
+
+        let version = Version::parse(version)?;
+
+        Ok(Self {
+            manager: parse_package_manager(name, &version)?,
+            version,
+            hash,
+        })
+    }
+}
+
+impl PackageJson {
+    // Parses the `packageManager` field, or returns `None` if not defined.
+    pub fn parse_package_manager(
+        &self,
+    ) -> Result<Option<PackageManagerField>, PackageManagerFieldError> {
+        self.package_manager
+            .as_deref()
+            .map(PackageManagerField::from_str)
+            .transpose()
+    }
+}
+
+impl TryFrom<String> for PackageManagerField {
+    type Error = PackageManagerFieldError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_str(&value)
+    }
+}
+
+impl From<PackageManagerField> for String {
+    fn from(value: PackageManagerField) -> String {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for PackageManagerField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}@{}", self.name(), self.version)?;
+
+        if let Some(hash) = &self.hash {
+            write!(f, "+{}.{}", hash.algorithm, hash.digest)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use super::workspace::*;
+use crate::range::{Range, RangeError};
 use regex::Regex;
-use semver::{Version, VersionReq};
+use semver::Version;
 use serde::Deserialize;
 use std::fmt;
 use std::path::PathBuf;
@@ -8,35 +9,51 @@ use std::str::FromStr;
 use std::sync::LazyLock;
 use thiserror::Error;
 
-static PROTOCOL: LazyLock<Regex> = LazyLock::new(|| Regex::new("^(?<protocol>[a-z+]+):").unwrap());
+// Protocols that are parsed before GitHub shorthands, versions, ranges, and tags.
+// The patterns below are also used by the JSON schema.
+const PROTOCOLS: [&str; 19] = [
+    "bitbucket",
+    "catalog",
+    "exec",
+    "file",
+    "gist",
+    "git",
+    "git+file",
+    "git+http",
+    "git+https",
+    "git+ssh",
+    "gitlab",
+    "http",
+    "https",
+    "jsr",
+    "link",
+    "npm",
+    "patch",
+    "portal",
+    "workspace",
+];
 
-static GITHUB: LazyLock<Regex> = LazyLock::new(|| {
-    Regex::new(
-        "(?<owner>[A-Za-z0-9_.-]+)/(?<repo>[A-Za-z0-9_.-]+)(?:#(?<commit>[A-Za-z0-9_.-/]+))?",
-    )
-    .unwrap()
-});
+pub(crate) const GITHUB_PATTERN: &str =
+    r"^(?:github:)?([A-Za-z0-9_.-]+)/([A-Za-z0-9_.-]+)(?:#(.+))?$";
+
+// Tags must not require URI encoding.
+pub(crate) const TAG_PATTERN: &str = r"^[A-Za-z0-9_.!~*'()-]+$";
+
+pub(crate) static PROTOCOL_PATTERN: LazyLock<String> =
+    LazyLock::new(|| format!("^({}):", PROTOCOLS.map(regex::escape).join("|")));
+
+static PROTOCOL: LazyLock<Regex> = LazyLock::new(|| Regex::new(&PROTOCOL_PATTERN).unwrap());
+
+static GITHUB: LazyLock<Regex> = LazyLock::new(|| Regex::new(GITHUB_PATTERN).unwrap());
+
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(TAG_PATTERN).unwrap());
 
 #[derive(Debug, Error)]
 #[cfg_attr(feature = "miette", derive(miette::Diagnostic))]
 pub enum VersionProtocolError {
-    #[error("Missing start version for range.")]
-    #[cfg_attr(
-        feature = "miette",
-        diagnostic(code(package_json::version::missing_range_start))
-    )]
-    RangeMissingStartVersion,
-
-    #[error("Missing stop version for range.")]
-    #[cfg_attr(
-        feature = "miette",
-        diagnostic(code(package_json::version::missing_range_stop))
-    )]
-    RangeMissingStopVersion,
-
-    #[error("Failed to parse version or requirement: {0}")]
-    #[cfg_attr(feature = "miette", diagnostic(code(package_json::version::invalid)))]
-    Semver(#[from] semver::Error),
+    #[error(transparent)]
+    #[cfg_attr(feature = "miette", diagnostic(transparent))]
+    Range(#[from] RangeError),
 
     #[error(transparent)]
     #[cfg_attr(feature = "miette", diagnostic(transparent))]
@@ -48,6 +65,15 @@ pub enum VersionProtocolError {
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
 #[serde(untagged, try_from = "String", into = "String")]
 pub enum VersionProtocol {
+    // npm:package@version, npm:package
+    Alias {
+        package: String,
+        requirement: Option<Box<VersionProtocol>>,
+    },
+    // catalog:, catalog:name
+    Catalog(Option<String>),
+    // exec:./script.js
+    Exec(PathBuf),
     File(PathBuf),
     Git {
         reference: Option<String>,
@@ -58,10 +84,26 @@ pub enum VersionProtocol {
         owner: String,
         repo: String,
     },
+    // jsr:@scope/package@version
+    Jsr {
+        package: Option<String>,
+        requirement: Range,
+    },
     Link(PathBuf),
+    // patch:package@npm%3A1.2.3#./patches/package.patch
+    Patch {
+        package: String,
+        requirement: Box<VersionProtocol>,
+        paths: Vec<PathBuf>,
+        params: Option<String>,
+    },
     Portal(PathBuf),
-    Range(Vec<VersionReq>),
-    Requirement(VersionReq),
+    // 1.2.3 || ^2
+    Range(Range),
+    // ^1.2.3, >=1 <2, 1.2.x, etc
+    Requirement(Range),
+    // latest, next, etc
+    Tag(String),
     Url(String),
     Version(Version),
     Workspace(WorkspaceProtocol),
@@ -72,18 +114,19 @@ impl FromStr for VersionProtocol {
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
         if value.is_empty() || value == "*" {
-            return Ok(VersionProtocol::Requirement(VersionReq::parse("*")?));
+            return Ok(VersionProtocol::Requirement(Range::any()));
         }
 
         if let Some(caps) = PROTOCOL.captures(value) {
-            let protocol = caps.name("protocol").unwrap().as_str();
+            let protocol = caps.get(1).unwrap().as_str();
             let index = protocol.len();
 
             match protocol {
                 "http" | "https" => {
                     return Ok(VersionProtocol::Url(value.to_owned()));
                 }
-                "git" | "git+ssh" | "git+http" | "git+https" | "git+file" => {
+                "git" | "git+ssh" | "git+http" | "git+https" | "git+file" | "bitbucket"
+                | "gist" | "gitlab" => {
                     let mut parts = value.split('#');
 
                     return Ok(VersionProtocol::Git {
@@ -91,9 +134,72 @@ impl FromStr for VersionProtocol {
                         reference: parts.next().map(|p| p.to_owned()),
                     });
                 }
+                "catalog" => {
+                    let name = &value[index + 1..];
+
+                    return Ok(VersionProtocol::Catalog(if name.is_empty() {
+                        None
+                    } else {
+                        Some(name.to_owned())
+                    }));
+                }
                 "file" => {
                     return Ok(VersionProtocol::File(PathBuf::from(&value[index + 1..])));
                 }
+                "exec" => {
+                    return Ok(VersionProtocol::Exec(PathBuf::from(&value[index + 1..])));
+                }
+                "jsr" => {
+                    let (package, requirement) = split_descriptor(&value[index + 1..]);
+
+                    return Ok(VersionProtocol::Jsr {
+                        package: package.map(|p| p.to_owned()),
+                        requirement: if requirement.is_empty() {
+                            Range::any()
+                        } else {
+                            Range::parse(requirement)?
+                        },
+                    });
+                }
+                "npm" => {
+                    let (package, requirement) = split_descriptor(&value[index + 1..]);
+
+                    // npm:1.2.3 is only a version without a package
+                    let Some(package) = package else {
+                        return VersionProtocol::from_str(requirement);
+                    };
+
+                    return Ok(VersionProtocol::Alias {
+                        package: package.to_owned(),
+                        requirement: if requirement.is_empty() {
+                            None
+                        } else {
+                            Some(Box::new(VersionProtocol::from_str(requirement)?))
+                        },
+                    });
+                }
+                "patch" => {
+                    let value = &value[index + 1..];
+                    let (source, selector) = value.split_once('#').unwrap_or((value, ""));
+                    let (selector, params) = match selector.split_once("::") {
+                        Some((selector, params)) => (selector, Some(params.to_owned())),
+                        None => (selector, None),
+                    };
+
+                    let source = decode_unsafe_chars(source);
+                    let (package, requirement) = split_descriptor(&source);
+
+                    return Ok(VersionProtocol::Patch {
+                        package: package.unwrap_or_default().to_owned(),
+                        requirement: Box::new(VersionProtocol::from_str(requirement)?),
+                        paths: selector
+                            .split('&')
+                            .filter(|path| !path.is_empty())
+                            .map(|path| PathBuf::from(decode_unsafe_chars(path)))
+                            .collect(),
+                        params,
+                    });
+                }
                 "link" => {
                     return Ok(VersionProtocol::Link(PathBuf::from(&value[index + 1..])));
                 }
@@ -111,49 +217,81 @@ impl FromStr for VersionProtocol {
 
         if let Some(caps) = GITHUB.captures(value) {
             return Ok(VersionProtocol::GitHub {
-                owner: caps.name("owner").unwrap().as_str().to_owned(),
-                repo: caps.name("repo").unwrap().as_str().to_owned(),
-                reference: caps.name("commit").map(|c| c.as_str().to_owned()),
+                owner: caps.get(1).unwrap().as_str().to_owned(),
+                repo: caps.get(2).unwrap().as_str().to_owned(),
+                reference: caps.get(3).map(|c| c.as_str().to_owned()),
             });
         }
 
-        if value.contains('-') {
-            let mut parts = value.split('-');
-            let l = parts
-                .next()
-                .ok_or(VersionProtocolError::RangeMissingStartVersion)?
-                .trim();
-            let r = parts
-                .next()
-                .ok_or(VersionProtocolError::RangeMissingStopVersion)?
-                .trim();
-
-            return Ok(VersionProtocol::Requirement(VersionReq::parse(&format!(
-                ">={l}, <={r}"
-            ))?));
+        if let Ok(version) = Version::parse(value) {
+            return Ok(VersionProtocol::Version(version));
         }
 
-        if value.contains("||") {
-            let mut ranges = vec![];
+        let range = match Range::parse(value) {
+            Ok(range) => range,
+            Err(error) => {
+                if is_tag(value) {
+                    return Ok(VersionProtocol::Tag(value.to_owned()));
+                }
 
-            for range in value.split("||") {
-                ranges.push(VersionReq::parse(range.trim())?);
+                return Err(error.into());
             }
+        };
 
-            return Ok(VersionProtocol::Range(ranges));
+        if value.contains("||") {
+            return Ok(VersionProtocol::Range(range));
         }
 
-        if value.contains('^')
-            || value.contains('~')
-            || value.contains('>')
-            || value.contains('<')
-            || value.contains('=')
-        {
-            return Ok(VersionProtocol::Requirement(VersionReq::parse(value)?));
+        Ok(VersionProtocol::Requirement(range))
+    }
+}
+
+// Returns the package name (if any) and the requirement
+// of a `package@requirement` descriptor.
+fn split_descriptor(value: &str) -> (Option<&str>, &str) {
+    // Skip the leading @ of scoped packages
+    match value.char_indices().skip(1).find(|(_, c)| *c == '@') {
+        Some((at, _)) => (Some(&value[..at]), &value[at + 1..]),
+        None if value.starts_with('@') || Range::parse(value).is_err() => (Some(value), ""),
+        None => (None, value),
+    }
+}
+
+fn is_tag(value: &str) -> bool {
+    TAG.is_match(value)
+}
+
+// Yarn only encodes the characters that are meaningful in a range.
+fn encode_unsafe_chars(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace(':', "%3A")
+        .replace('#', "%23")
+}
+
+fn decode_unsafe_chars(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] == b'%' {
+            if let Some(byte) = bytes
+                .get(index + 1..index + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                decoded.push(byte);
+                index += 3;
+                continue;
+            }
         }
 
-        Ok(VersionProtocol::Version(Version::parse(value)?))
+        decoded.push(bytes[index]);
+        index += 1;
     }
+
+    String::from_utf8_lossy(&decoded).into_owned()
 }
 
 impl TryFrom<String> for VersionProtocol {
@@ -176,6 +314,16 @@ impl fmt::Display for VersionProtocol {
             f,
             "{}",
             match self {
+                VersionProtocol::Alias {
+                    package,
+                    requirement,
+                } => match requirement {
+                    Some(requirement) => format!("npm:{package}@{requirement}"),
+                    None => format!("npm:{package}"),
+                },
+                VersionProtocol::Catalog(name) =>
+                    format!("catalog:{}", name.as_deref().unwrap_or_default()),
+                VersionProtocol::Exec(path) => format!("exec:{}", path.display()),
                 VersionProtocol::File(path) => format!("file:{}", path.display()),
                 VersionProtocol::Git { reference, url } => reference
                     .as_ref()
@@ -193,14 +341,45 @@ impl fmt::Display for VersionProtocol {
                         .map(|c| format!("{github}#{c}"))
                         .unwrap_or_else(|| github)
                 }
+                VersionProtocol::Jsr {
+                    package,
+                    requirement,
+                } => match package {
+                    Some(package) => format!("jsr:{package}@{requirement}"),
+                    None => format!("jsr:{requirement}"),
+                },
                 VersionProtocol::Link(path) => format!("link:{}", path.display()),
+                VersionProtocol::Patch {
+                    package,
+                    requirement,
+                    paths,
+                    params,
+                } => {
+                    let source = match requirement.as_ref() {
+                        VersionProtocol::Range(_)
+                        | VersionProtocol::Requirement(_)
+                        | VersionProtocol::Tag(_)
+                        | VersionProtocol::Version(_) => format!("{package}@npm:{requirement}"),
+                        _ => format!("{package}@{requirement}"),
+                    };
+                    let selector = paths
+                        .iter()
+                        .map(|path| encode_unsafe_chars(&path.to_string_lossy()))
+                        .collect::<Vec<_>>()
+                        .join("&");
+
+                    match params {
+                        Some(params) => format!(
+                            "patch:{}#{selector}::{params}",
+                            encode_unsafe_chars(&source)
+                        ),
+                        None => format!("patch:{}#{selector}", encode_unsafe_chars(&source)),
+                    }
+                }
                 VersionProtocol::Portal(path) => format!("portal:{}", path.display()),
-                VersionProtocol::Range(range) => range
-                    .iter()
-                    .map(|r| r.to_string())
-                    .collect::<Vec<_>>()
-                    .join(" || "),
+                VersionProtocol::Range(range) => range.to_string(),
                 VersionProtocol::Requirement(req) => req.to_string(),
+                VersionProtocol::Tag(tag) => tag.to_owned(),
                 VersionProtocol::Url(url) => url.to_owned(),
                 VersionProtocol::Version(ver) => ver.to_string(),
                 VersionProtocol::Workspace(ws) => format!("workspace:{ws}"),
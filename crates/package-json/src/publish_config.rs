@@ -0,0 +1,124 @@
+use crate::import_export::ImportExportField;
+use crate::metadata_fields::BinField;
+use crate::package_json::{BrowserField, PackageJson};
+use crate::types_resolver::TypesVersionsMap;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+use std::path::PathBuf;
+
+// Fields that pnpm allows to be overridden, that we don't explicitly support.
+// https://github.com/pnpm/pnpm/blob/main/pkg-manifest/exportable-manifest/src/overridePublishConfig.ts
+const OVERRIDABLE_OTHER_FIELDS: &[&str] = &[
+    "cpu", "es2015", "esnext", "libc", "os", "type", "umd:main", "unpkg",
+];
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum PublishAccess {
+    Public,
+    Restricted,
+}
+
+// https://docs.npmjs.com/cli/v10/configuring-npm/package-json#publishconfig
+// https://pnpm.io/package_json#publishconfig
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PublishConfigField {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access: Option<PublishAccess>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+
+    // pnpm
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directory: Option<PathBuf>,
+
+    // Overrides
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bin: Option<BinField>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub browser: Option<BrowserField>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exports: Option<ImportExportField>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub main: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub types: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typings: Option<PathBuf>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub types_versions: Option<TypesVersionsMap>,
+
+    #[serde(flatten)]
+    pub other_fields: FxHashMap<String, serde_json::Value>,
+}
+
+impl PackageJson {
+    // Overlays the entry point fields in `publishConfig` onto the manifest,
+    // like pnpm and Yarn do when publishing. Registry settings, like `access`
+    // and `registry`, remain in `publishConfig` for the publisher to use.
+    pub fn apply_publish_config(&mut self) {
+        let Some(config) = self.publish_config.clone() else {
+            return;
+        };
+
+        if config.bin.is_some() {
+            self.bin = config.bin;
+        }
+
+        if config.browser.is_some() {
+            self.browser = config.browser;
+        }
+
+        if config.exports.is_some() {
+            self.exports = config.exports;
+        }
+
+        if config.main.is_some() {
+            self.main = config.main;
+        }
+
+        if config.module.is_some() {
+            self.module = config.module;
+        }
+
+        if config.types.is_some() {
+            self.types = config.types;
+        }
+
+        if config.typings.is_some() {
+            self.typings = config.typings;
+        }
+
+        if config.types_versions.is_some() {
+            self.types_versions = config.types_versions;
+        }
+
+        for (key, value) in config.other_fields {
+            if key == "type" {
+                if let Some(type_of) = value.as_str() {
+                    self.type_of = Some(type_of.to_owned());
+                }
+            } else if OVERRIDABLE_OTHER_FIELDS.contains(&key.as_str()) {
+                self.other_fields.insert(key, value);
+            }
+        }
+    }
+}
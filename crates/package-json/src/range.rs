@@ -0,0 +1,959 @@
+// Based on node-semver, which differs from Cargo's semver semantics.
+// https://github.com/npm/node-semver#ranges
+
+use semver::{BuildMetadata, Prerelease, Version};
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "miette", derive(miette::Diagnostic))]
+pub enum RangeError {
+    #[error("Invalid comparator `{0}` in version range.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::range::invalid_comparator))
+    )]
+    InvalidComparator(String),
+
+    #[error("Failed to parse version in range: {0}")]
+    #[cfg_attr(feature = "miette", diagnostic(code(package_json::range::invalid)))]
+    Semver(#[from] semver::Error),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Comparator {
+    Any,
+    Eq(Version),
+    Gt(Version),
+    Gte(Version),
+    Lt(Version),
+    Lte(Version),
+}
+
+impl Comparator {
+    pub fn version(&self) -> Option<&Version> {
+        match self {
+            Self::Any => None,
+            Self::Eq(ver) | Self::Gt(ver) | Self::Gte(ver) | Self::Lt(ver) | Self::Lte(ver) => {
+                Some(ver)
+            }
+        }
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Eq(ver) => compare(version, ver).is_eq(),
+            Self::Gt(ver) => compare(version, ver).is_gt(),
+            Self::Gte(ver) => compare(version, ver).is_ge(),
+            Self::Lt(ver) => compare(version, ver).is_lt(),
+            Self::Lte(ver) => compare(version, ver).is_le(),
+        }
+    }
+
+    // Comparator.intersects
+    pub fn intersects(&self, other: &Comparator) -> bool {
+        let (a, b) = match (self, other) {
+            (Self::Any, _) | (_, Self::Any) => return true,
+            (Self::Eq(ver), _) => return test_set(&[other.clone()], ver),
+            (_, Self::Eq(ver)) => return test_set(&[self.clone()], ver),
+            (a, b) => (a, b),
+        };
+
+        if a.is_null_set() || b.is_null_set() {
+            return false;
+        }
+
+        let a_ver = a.version().unwrap();
+        let b_ver = b.version().unwrap();
+
+        // Same direction
+        if a.is_greater() && b.is_greater() || a.is_less() && b.is_less() {
+            return true;
+        }
+
+        // Same version and both inclusive
+        if compare(a_ver, b_ver).is_eq() && a.is_inclusive() && b.is_inclusive() {
+            return true;
+        }
+
+        // Opposite directions
+        compare(a_ver, b_ver).is_lt() && a.is_greater() && b.is_less()
+            || compare(a_ver, b_ver).is_gt() && a.is_less() && b.is_greater()
+    }
+
+    fn is_greater(&self) -> bool {
+        matches!(self, Self::Gt(_) | Self::Gte(_))
+    }
+
+    fn is_less(&self) -> bool {
+        matches!(self, Self::Lt(_) | Self::Lte(_))
+    }
+
+    fn is_inclusive(&self) -> bool {
+        matches!(self, Self::Eq(_) | Self::Gte(_) | Self::Lte(_))
+    }
+
+    // <0.0.0, <0.0.0-0, etc
+    fn is_null_set(&self) -> bool {
+        matches!(self, Self::Lt(ver) if ver.major == 0 && ver.minor == 0 && ver.patch == 0)
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Any => write!(f, "*"),
+            Self::Eq(ver) => write!(f, "{ver}"),
+            Self::Gt(ver) => write!(f, ">{ver}"),
+            Self::Gte(ver) => write!(f, ">={ver}"),
+            Self::Lt(ver) => write!(f, "<{ver}"),
+            Self::Lte(ver) => write!(f, "<={ver}"),
+        }
+    }
+}
+
+// https://github.com/npm/node-semver#ranges
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[serde(try_from = "String", into = "String")]
+pub struct Range {
+    raw: String,
+    sets: Vec<Vec<Comparator>>,
+}
+
+impl Range {
+    pub fn parse<T: AsRef<str>>(value: T) -> Result<Self, RangeError> {
+        let raw = value.as_ref().trim();
+        let mut sets = vec![];
+
+        for set in raw.split("||") {
+            sets.push(parse_comparator_set(set.trim())?);
+        }
+
+        // Throw out null sets if we have others
+        if sets.len() > 1 && sets.iter().any(|set| !set[0].is_null_set()) {
+            sets.retain(|set| !set[0].is_null_set());
+        }
+
+        // If any set matches everything, the whole range does
+        if sets
+            .iter()
+            .any(|set| set.len() == 1 && set[0] == Comparator::Any)
+        {
+            sets = vec![vec![Comparator::Any]];
+        }
+
+        Ok(Self {
+            raw: raw.to_owned(),
+            sets,
+        })
+    }
+
+    pub fn any() -> Self {
+        Self {
+            raw: "*".into(),
+            sets: vec![vec![Comparator::Any]],
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn comparator_sets(&self) -> &[Vec<Comparator>] {
+        &self.sets
+    }
+
+    pub fn is_any(&self) -> bool {
+        self.sets.len() == 1 && self.sets[0].len() == 1 && self.sets[0][0] == Comparator::Any
+    }
+
+    pub fn satisfies(&self, version: &Version) -> bool {
+        self.sets.iter().any(|set| test_set(set, version))
+    }
+
+    pub fn max_satisfying<'v, I>(&self, versions: I) -> Option<&'v Version>
+    where
+        I: IntoIterator<Item = &'v Version>,
+    {
+        versions
+            .into_iter()
+            .filter(|ver| self.satisfies(ver))
+            .max_by(|a, b| compare(a, b))
+    }
+
+    // minVersion
+    pub fn min_version(&self) -> Option<Version> {
+        for candidate in [Version::new(0, 0, 0), Version::parse("0.0.0-0").unwrap()] {
+            if self.satisfies(&candidate) {
+                return Some(candidate);
+            }
+        }
+
+        let mut min: Option<Version> = None;
+
+        for set in &self.sets {
+            let mut set_min: Option<Version> = None;
+
+            for comparator in set {
+                let version = match comparator {
+                    Comparator::Gt(ver) => {
+                        let mut ver = ver.clone();
+
+                        if ver.pre.is_empty() {
+                            ver.patch += 1;
+                        } else {
+                            ver.pre = Prerelease::new(&format!("{}.0", ver.pre)).unwrap();
+                        }
+
+                        ver
+                    }
+                    Comparator::Eq(ver) | Comparator::Gte(ver) => ver.clone(),
+                    _ => continue,
+                };
+
+                if set_min
+                    .as_ref()
+                    .map_or(true, |min| compare(&version, min).is_gt())
+                {
+                    set_min = Some(version);
+                }
+            }
+
+            if let Some(set_min) = set_min {
+                if min
+                    .as_ref()
+                    .map_or(true, |min| compare(min, &set_min).is_gt())
+                {
+                    min = Some(set_min);
+                }
+            }
+        }
+
+        min.filter(|min| self.satisfies(min))
+    }
+
+    // Range.intersects
+    pub fn intersects(&self, other: &Range) -> bool {
+        self.sets.iter().any(|set| {
+            is_satisfiable(set)
+                && other.sets.iter().any(|other_set| {
+                    is_satisfiable(other_set)
+                        && set
+                            .iter()
+                            .all(|a| other_set.iter().all(|b| a.intersects(b)))
+                })
+        })
+    }
+
+    // Returns a range that only matches versions matched by both ranges,
+    // or `None` if they have no versions in common.
+    pub fn intersect(&self, other: &Range) -> Option<Range> {
+        let mut sets = vec![];
+
+        for a in &self.sets {
+            for b in &other.sets {
+                if let Some(set) = intersect_sets(a, b) {
+                    if !sets.contains(&set) {
+                        sets.push(set);
+                    }
+                }
+            }
+        }
+
+        if sets.is_empty() {
+            return None;
+        }
+
+        if sets.iter().any(|set| set[0] == Comparator::Any) {
+            return Some(Self::any());
+        }
+
+        Some(Self {
+            raw: sets
+                .iter()
+                .map(|set| {
+                    set.iter()
+                        .map(|comp| comp.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join(" || "),
+            sets,
+        })
+    }
+
+    // Returns true if every version matched by this range
+    // is also matched by the other range.
+    pub fn subset(&self, other: &Range) -> bool {
+        if self.raw == other.raw {
+            return true;
+        }
+
+        let mut saw_non_null = false;
+
+        'outer: for sub in &self.sets {
+            for dom in &other.sets {
+                let is_sub = simple_subset(sub, dom);
+
+                saw_non_null = saw_non_null || is_sub.is_some();
+
+                if is_sub == Some(true) {
+                    continue 'outer;
+                }
+            }
+
+            if saw_non_null {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+impl Default for Range {
+    fn default() -> Self {
+        Self::any()
+    }
+}
+
+impl FromStr for Range {
+    type Err = RangeError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Self::parse(value)
+    }
+}
+
+impl TryFrom<String> for Range {
+    type Error = RangeError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::parse(value)
+    }
+}
+
+impl From<Range> for String {
+    fn from(value: Range) -> String {
+        value.raw
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+// Like `Version::cmp` but ignores build metadata.
+fn compare(a: &Version, b: &Version) -> Ordering {
+    (a.major, a.minor, a.patch, &a.pre).cmp(&(b.major, b.minor, b.patch, &b.pre))
+}
+
+fn test_set(set: &[Comparator], version: &Version) -> bool {
+    if !set.iter().all(|comp| comp.matches(version)) {
+        return false;
+    }
+
+    if version.pre.is_empty() {
+        return true;
+    }
+
+    // Prereleases only match if a comparator in the set
+    // has a prerelease on the same [major, minor, patch] tuple
+    set.iter().any(|comp| {
+        comp.version().is_some_and(|ver| {
+            !ver.pre.is_empty()
+                && ver.major == version.major
+                && ver.minor == version.minor
+                && ver.patch == version.patch
+        })
+    })
+}
+
+fn is_satisfiable(set: &[Comparator]) -> bool {
+    set.iter()
+        .enumerate()
+        .all(|(i, a)| set[i + 1..].iter().all(|b| a.intersects(b)))
+}
+
+// Reduces both sets to the highest lower bound and the lowest upper bound.
+fn intersect_sets(a: &[Comparator], b: &[Comparator]) -> Option<Vec<Comparator>> {
+    let mut eq: Option<&Version> = None;
+    let mut gt: Option<&Comparator> = None;
+    let mut lt: Option<&Comparator> = None;
+
+    for comp in a.iter().chain(b) {
+        match comp {
+            Comparator::Any => {}
+            Comparator::Eq(ver) => {
+                if eq.is_some_and(|eq| compare(eq, ver).is_ne()) {
+                    return None;
+                }
+
+                eq = Some(ver);
+            }
+            Comparator::Gt(_) | Comparator::Gte(_) => gt = Some(higher_gt(gt, comp)),
+            Comparator::Lt(_) | Comparator::Lte(_) => lt = Some(lower_lt(lt, comp)),
+        };
+    }
+
+    if lt.is_some_and(|lt| lt.is_null_set()) {
+        return None;
+    }
+
+    if let Some(eq) = eq {
+        let set = [gt, lt].into_iter().flatten().cloned().collect::<Vec<_>>();
+
+        return set
+            .iter()
+            .all(|comp| comp.matches(eq))
+            .then(|| vec![Comparator::Eq(eq.clone())]);
+    }
+
+    match (gt, lt) {
+        (Some(gt), Some(lt)) => {
+            let gt_ver = gt.version().unwrap();
+
+            match compare(gt_ver, lt.version().unwrap()) {
+                Ordering::Greater => None,
+                Ordering::Equal => (gt.is_inclusive() && lt.is_inclusive())
+                    .then(|| vec![Comparator::Eq(gt_ver.clone())]),
+                Ordering::Less => Some(vec![gt.clone(), lt.clone()]),
+            }
+        }
+        (Some(comp), None) | (None, Some(comp)) => Some(vec![comp.clone()]),
+        (None, None) => Some(vec![Comparator::Any]),
+    }
+}
+
+fn higher_gt<'a>(a: Option<&'a Comparator>, b: &'a Comparator) -> &'a Comparator {
+    let Some(a) = a else {
+        return b;
+    };
+
+    match compare(a.version().unwrap(), b.version().unwrap()) {
+        Ordering::Greater => a,
+        Ordering::Less => b,
+        Ordering::Equal => {
+            if matches!(b, Comparator::Gt(_)) && matches!(a, Comparator::Gte(_)) {
+                b
+            } else {
+                a
+            }
+        }
+    }
+}
+
+fn lower_lt<'a>(a: Option<&'a Comparator>, b: &'a Comparator) -> &'a Comparator {
+    let Some(a) = a else {
+        return b;
+    };
+
+    match compare(a.version().unwrap(), b.version().unwrap()) {
+        Ordering::Less => a,
+        Ordering::Greater => b,
+        Ordering::Equal => {
+            if matches!(b, Comparator::Lt(_)) && matches!(a, Comparator::Lte(_)) {
+                b
+            } else {
+                a
+            }
+        }
+    }
+}
+
+fn has_same_prerelease_tuple(comp: &Comparator, version: &Version) -> bool {
+    comp.version().is_some_and(|ver| {
+        !ver.pre.is_empty()
+            && ver.major == version.major
+            && ver.minor == version.minor
+            && ver.patch == version.patch
+    })
+}
+
+// Based on `simpleSubset` in node-semver. Returns `None` when
+// the subset set is a null set (can never match).
+fn simple_subset(sub: &[Comparator], dom: &[Comparator]) -> Option<bool> {
+    let minimum = [Comparator::Gte(Version::new(0, 0, 0))];
+    let is_any = |set: &[Comparator]| set.len() == 1 && set[0] == Comparator::Any;
+
+    let mut sub = sub;
+    let mut dom = dom;
+
+    if is_any(sub) {
+        if is_any(dom) {
+            return Some(true);
+        }
+
+        sub = &minimum;
+    }
+
+    if is_any(dom) {
+        dom = &minimum;
+    }
+
+    let mut eq_set: Vec<&Version> = vec![];
+    let mut gt: Option<&Comparator> = None;
+    let mut lt: Option<&Comparator> = None;
+
+    for comp in sub {
+        match comp {
+            Comparator::Gt(_) | Comparator::Gte(_) => gt = Some(higher_gt(gt, comp)),
+            Comparator::Lt(_) | Comparator::Lte(_) => lt = Some(lower_lt(lt, comp)),
+            Comparator::Eq(ver) => {
+                if !eq_set.iter().any(|eq| compare(eq, ver).is_eq()) {
+                    eq_set.push(ver);
+                }
+            }
+            Comparator::Any => {}
+        };
+    }
+
+    if eq_set.len() > 1 {
+        return None;
+    }
+
+    let mut gtlt_comp = None;
+
+    if let (Some(gt), Some(lt)) = (gt, lt) {
+        let comp = compare(gt.version().unwrap(), lt.version().unwrap());
+
+        if comp.is_gt()
+            || comp.is_eq()
+                && (!matches!(gt, Comparator::Gte(_)) || !matches!(lt, Comparator::Lte(_)))
+        {
+            return None;
+        }
+
+        gtlt_comp = Some(comp);
+    }
+
+    if let Some(eq) = eq_set.first() {
+        if gt.is_some_and(|gt| !test_set(&[gt.clone()], eq))
+            || lt.is_some_and(|lt| !test_set(&[lt.clone()], eq))
+        {
+            return None;
+        }
+
+        return Some(dom.iter().all(|comp| test_set(&[comp.clone()], eq)));
+    }
+
+    // If the subset has a prerelease, we need a comparator in the
+    // superset with the same tuple and a prerelease
+    let mut need_dom_lt_pre = lt
+        .and_then(|lt| lt.version())
+        .filter(|ver| !ver.pre.is_empty());
+    let mut need_dom_gt_pre = gt
+        .and_then(|gt| gt.version())
+        .filter(|ver| !ver.pre.is_empty());
+
+    // Exception: <1.2.3-0 is the same as <1.2.3
+    if need_dom_lt_pre.is_some_and(|ver| ver.pre.as_str() == "0")
+        && matches!(lt, Some(Comparator::Lt(_)))
+    {
+        need_dom_lt_pre = None;
+    }
+
+    let mut has_dom_lt = false;
+    let mut has_dom_gt = false;
+    let is_equal_bounds = gtlt_comp.is_some_and(|comp| comp.is_eq());
+
+    for comp in dom {
+        has_dom_gt = has_dom_gt || comp.is_greater();
+        has_dom_lt = has_dom_lt || comp.is_less();
+
+        if let Some(gt) = gt {
+            if need_dom_gt_pre.is_some_and(|ver| has_same_prerelease_tuple(comp, ver)) {
+                need_dom_gt_pre = None;
+            }
+
+            if comp.is_greater() {
+                let higher = higher_gt(Some(gt), comp);
+
+                if std::ptr::eq(higher, comp) && !std::ptr::eq(higher, gt) {
+                    return Some(false);
+                }
+            } else if matches!(gt, Comparator::Gte(_))
+                && !test_set(&[comp.clone()], gt.version().unwrap())
+            {
+                return Some(false);
+            }
+        }
+
+        if let Some(lt) = lt {
+            if need_dom_lt_pre.is_some_and(|ver| has_same_prerelease_tuple(comp, ver)) {
+                need_dom_lt_pre = None;
+            }
+
+            if comp.is_less() {
+                let lower = lower_lt(Some(lt), comp);
+
+                if std::ptr::eq(lower, comp) && !std::ptr::eq(lower, lt) {
+                    return Some(false);
+                }
+            } else if matches!(lt, Comparator::Lte(_))
+                && !test_set(&[comp.clone()], lt.version().unwrap())
+            {
+                return Some(false);
+            }
+        }
+
+        if matches!(comp, Comparator::Eq(_)) && (gt.is_some() || lt.is_some()) && !is_equal_bounds {
+            return Some(false);
+        }
+    }
+
+    // If there was a < or >, and nothing in the dom, then must be false,
+    // unless it was limited by another range in the other direction
+    if gt.is_some() && has_dom_lt && lt.is_none() && !is_equal_bounds
+        || lt.is_some() && has_dom_gt && gt.is_none() && !is_equal_bounds
+    {
+        return Some(false);
+    }
+
+    if need_dom_gt_pre.is_some() || need_dom_lt_pre.is_some() {
+        return Some(false);
+    }
+
+    Some(true)
+}
+
+const MAX_SAFE_INTEGER: u64 = 9_007_199_254_740_991;
+
+// A version where any part may be omitted or an `x`, `X`, or `*` wildcard.
+struct PartialVersion {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    pre: Prerelease,
+}
+
+impl PartialVersion {
+    fn parse(value: &str) -> Result<Self, RangeError> {
+        let invalid = || RangeError::InvalidComparator(value.to_owned());
+        let value = value.trim_start_matches(['v', '=']).trim();
+
+        // Build metadata is ignored when comparing
+        let (value, build) = value.split_once('+').unwrap_or((value, ""));
+        let (value, pre) = value.split_once('-').unwrap_or((value, ""));
+
+        let mut parts = [None, None, None];
+        let mut wildcard = false;
+        let mut count = 0;
+
+        for (index, part) in value.split('.').enumerate() {
+            if index > 2 || part.is_empty() {
+                return Err(invalid());
+            }
+
+            count += 1;
+
+            if part == "x" || part == "X" || part == "*" {
+                wildcard = true;
+            } else if !wildcard {
+                if part.len() > 1 && part.starts_with('0') {
+                    return Err(invalid());
+                }
+
+                let number = part.parse::<u64>().map_err(|_| invalid())?;
+
+                // Same limit as node-semver, which uses JavaScript numbers
+                if number > MAX_SAFE_INTEGER {
+                    return Err(invalid());
+                }
+
+                parts[index] = Some(number);
+            }
+        }
+
+        if (!pre.is_empty() || !build.is_empty()) && (wildcard || count != 3) {
+            return Err(invalid());
+        }
+
+        BuildMetadata::new(build)?;
+
+        Ok(Self {
+            major: parts[0],
+            minor: parts[1],
+            patch: parts[2],
+            pre: Prerelease::new(pre)?,
+        })
+    }
+
+    fn is_full(&self) -> bool {
+        self.patch.is_some()
+    }
+
+    fn to_version(&self) -> Version {
+        let mut version = Version::new(
+            self.major.unwrap_or(0),
+            self.minor.unwrap_or(0),
+            self.patch.unwrap_or(0),
+        );
+        version.pre = self.pre.clone();
+        version
+    }
+}
+
+fn new_version(major: u64, minor: u64, patch: u64, pre: &str) -> Version {
+    let mut version = Version::new(major, minor, patch);
+    version.pre = Prerelease::new(pre).unwrap();
+    version
+}
+
+fn parse_comparator_set(set: &str) -> Result<Vec<Comparator>, RangeError> {
+    // Join operators that are separated from their version by whitespace
+    let mut tokens: Vec<String> = vec![];
+    let mut pending = String::new();
+
+    for token in set.split_whitespace() {
+        pending.push_str(token);
+
+        if !token
+            .chars()
+            .all(|c| c == '<' || c == '>' || c == '=' || c == '~' || c == '^')
+        {
+            tokens.push(std::mem::take(&mut pending));
+        }
+    }
+
+    if !pending.is_empty() {
+        return Err(RangeError::InvalidComparator(pending));
+    }
+
+    let mut comparators = vec![];
+
+    // Hyphen range: 1.2.3 - 2.3.4
+    if tokens.len() == 3 && tokens[1] == "-" {
+        comparators.extend(parse_hyphen(&tokens[0], &tokens[2])?);
+    } else {
+        for token in &tokens {
+            comparators.extend(parse_comparator(token)?);
+        }
+    }
+
+    // If any comparator is a null set, the whole set is
+    if let Some(null) = comparators.iter().find(|comp| comp.is_null_set()) {
+        return Ok(vec![null.clone()]);
+    }
+
+    // Remove duplicates and any wildcards if there are other comparators
+    let mut deduped: Vec<Comparator> = vec![];
+
+    for comp in comparators {
+        if !deduped.contains(&comp) {
+            deduped.push(comp);
+        }
+    }
+
+    if deduped.len() > 1 {
+        deduped.retain(|comp| *comp != Comparator::Any);
+    }
+
+    if deduped.is_empty() {
+        deduped.push(Comparator::Any);
+    }
+
+    Ok(deduped)
+}
+
+fn parse_hyphen(from: &str, to: &str) -> Result<Vec<Comparator>, RangeError> {
+    let from = PartialVersion::parse(from)?;
+    let to_partial = PartialVersion::parse(to)?;
+    let mut comparators = vec![];
+
+    match (from.major, from.minor, from.patch) {
+        (None, _, _) => {}
+        (Some(major), None, _) => comparators.push(Comparator::Gte(Version::new(major, 0, 0))),
+        (Some(major), Some(minor), None) => {
+            comparators.push(Comparator::Gte(Version::new(major, minor, 0)))
+        }
+        _ => comparators.push(Comparator::Gte(from.to_version())),
+    };
+
+    match (to_partial.major, to_partial.minor, to_partial.patch) {
+        (None, _, _) => {}
+        (Some(major), None, _) => comparators.push(Comparator::Lt(new_version(
+            increment(major, to)?,
+            0,
+            0,
+            "0",
+        ))),
+        (Some(major), Some(minor), None) => comparators.push(Comparator::Lt(new_version(
+            major,
+            increment(minor, to)?,
+            0,
+            "0",
+        ))),
+        _ => comparators.push(Comparator::Lte(to_partial.to_version())),
+    };
+
+    Ok(comparators)
+}
+
+fn parse_comparator(token: &str) -> Result<Vec<Comparator>, RangeError> {
+    let (op, version) = if let Some(rest) = token.strip_prefix('^') {
+        ("^", rest)
+    } else if let Some(rest) = token.strip_prefix("~>") {
+        ("~", rest)
+    } else if let Some(rest) = token.strip_prefix('~') {
+        ("~", rest)
+    } else if let Some(rest) = token.strip_prefix(">=") {
+        (">=", rest)
+    } else if let Some(rest) = token.strip_prefix("<=") {
+        ("<=", rest)
+    } else if let Some(rest) = token.strip_prefix('>') {
+        (">", rest)
+    } else if let Some(rest) = token.strip_prefix('<') {
+        ("<", rest)
+    } else {
+        ("", token)
+    };
+
+    let partial = PartialVersion::parse(version)?;
+
+    match op {
+        "^" => desugar_caret(&partial, token),
+        "~" => desugar_tilde(&partial, token),
+        _ => desugar_xrange(op, &partial, token),
+    }
+}
+
+// Bumps a version part for an exclusive upper bound. Parts are limited to
+// `MAX_SAFE_INTEGER` when parsed, but we still guard against overflow.
+fn increment(value: u64, token: &str) -> Result<u64, RangeError> {
+    value
+        .checked_add(1)
+        .ok_or_else(|| RangeError::InvalidComparator(token.to_owned()))
+}
+
+// ^1.2.3 := >=1.2.3 <2.0.0-0
+fn desugar_caret(partial: &PartialVersion, token: &str) -> Result<Vec<Comparator>, RangeError> {
+    let PartialVersion {
+        major,
+        minor,
+        patch,
+        ..
+    } = partial;
+
+    Ok(match (major, minor, patch) {
+        (None, _, _) => vec![Comparator::Any],
+        (Some(major), None, _) => vec![
+            Comparator::Gte(Version::new(*major, 0, 0)),
+            Comparator::Lt(new_version(increment(*major, token)?, 0, 0, "0")),
+        ],
+        (Some(major), Some(minor), None) => vec![
+            Comparator::Gte(Version::new(*major, *minor, 0)),
+            Comparator::Lt(if *major == 0 {
+                new_version(0, increment(*minor, token)?, 0, "0")
+            } else {
+                new_version(increment(*major, token)?, 0, 0, "0")
+            }),
+        ],
+        (Some(major), Some(minor), Some(patch)) => vec![
+            Comparator::Gte(partial.to_version()),
+            Comparator::Lt(if *major == 0 && *minor == 0 {
+                new_version(0, 0, increment(*patch, token)?, "0")
+            } else if *major == 0 {
+                new_version(0, increment(*minor, token)?, 0, "0")
+            } else {
+                new_version(increment(*major, token)?, 0, 0, "0")
+            }),
+        ],
+    })
+}
+
+// ~1.2.3 := >=1.2.3 <1.3.0-0
+fn desugar_tilde(partial: &PartialVersion, token: &str) -> Result<Vec<Comparator>, RangeError> {
+    Ok(match (partial.major, partial.minor) {
+        (None, _) => vec![Comparator::Any],
+        (Some(major), None) => vec![
+            Comparator::Gte(Version::new(major, 0, 0)),
+            Comparator::Lt(new_version(increment(major, token)?, 0, 0, "0")),
+        ],
+        (Some(major), Some(minor)) => vec![
+            Comparator::Gte(partial.to_version()),
+            Comparator::Lt(new_version(major, increment(minor, token)?, 0, "0")),
+        ],
+    })
+}
+
+// 1.2.x := >=1.2.0 <1.3.0-0
+fn desugar_xrange(
+    op: &str,
+    partial: &PartialVersion,
+    token: &str,
+) -> Result<Vec<Comparator>, RangeError> {
+    if partial.is_full() {
+        let version = partial.to_version();
+
+        return Ok(vec![match op {
+            ">" => Comparator::Gt(version),
+            ">=" => Comparator::Gte(version),
+            "<" => Comparator::Lt(version),
+            "<=" => Comparator::Lte(version),
+            _ => Comparator::Eq(version),
+        }]);
+    }
+
+    let Some(major) = partial.major else {
+        return Ok(vec![if op == ">" || op == "<" {
+            Comparator::Lt(new_version(0, 0, 0, "0"))
+        } else {
+            Comparator::Any
+        }]);
+    };
+
+    Ok(match (op, partial.minor) {
+        // >1 := >=2.0.0, >1.2 := >=1.3.0
+        (">", None) => vec![Comparator::Gte(Version::new(
+            increment(major, token)?,
+            0,
+            0,
+        ))],
+        (">", Some(minor)) => vec![Comparator::Gte(Version::new(
+            major,
+            increment(minor, token)?,
+            0,
+        ))],
+        // >=1 := >=1.0.0
+        (">=", minor) => vec![Comparator::Gte(Version::new(major, minor.unwrap_or(0), 0))],
+        // <1 := <1.0.0-0
+        ("<", minor) => vec![Comparator::Lt(new_version(
+            major,
+            minor.unwrap_or(0),
+            0,
+            "0",
+        ))],
+        // <=1 := <2.0.0-0, <=1.2 := <1.3.0-0
+        ("<=", None) => vec![Comparator::Lt(new_version(
+            increment(major, token)?,
+            0,
+            0,
+            "0",
+        ))],
+        ("<=", Some(minor)) => vec![Comparator::Lt(new_version(
+            major,
+            increment(minor, token)?,
+            0,
+            "0",
+        ))],
+        // 1 := >=1.0.0 <2.0.0-0
+        (_, None) => vec![
+            Comparator::Gte(Version::new(major, 0, 0)),
+            Comparator::Lt(new_version(increment(major, token)?, 0, 0, "0")),
+        ],
+        // 1.2 := >=1.2.0 <1.3.0-0
+        (_, Some(minor)) => vec![
+            Comparator::Gte(Version::new(major, minor, 0)),
+            Comparator::Lt(new_version(major, increment(minor, token)?, 0, "0")),
+        ],
+    })
+}
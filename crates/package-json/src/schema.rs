@@ -0,0 +1,143 @@
+use crate::import_export::ImportExportKey;
+use crate::metadata_fields::*;
+use crate::range::Range;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{InstanceType, Metadata, Schema, SchemaObject, StringValidation};
+use schemars::JsonSchema;
+
+// Types that are deserialized from strings (or another shape) are not
+// supported by the derive, so their schemas are implemented manually.
+
+pub(crate) fn string_schema(description: &str, pattern: Option<&str>) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        metadata: Some(Box::new(Metadata {
+            description: Some(description.to_owned()),
+            ..Default::default()
+        })),
+        string: pattern.map(|pattern| {
+            Box::new(StringValidation {
+                pattern: Some(pattern.to_owned()),
+                ..Default::default()
+            })
+        }),
+        ..Default::default()
+    }
+    .into()
+}
+
+impl JsonSchema for ImportExportKey {
+    fn schema_name() -> String {
+        "ImportExportKey".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "A subpath (`.` or `./*`), an import specifier (`#*`), or a condition name.",
+            None,
+        )
+    }
+}
+
+impl JsonSchema for Range {
+    fn schema_name() -> String {
+        "Range".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            "A semver range, for example `^1.2.3`, `>=1 <2`, or `1.x || 2.x`.",
+            Some(r"^[ \^~<>=vV0-9xX*|.+A-Za-z-]*$"),
+        )
+    }
+}
+
+impl JsonSchema for LicenseField {
+    fn schema_name() -> String {
+        "LicenseField".into()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        LicenseValue::json_schema(gen)
+    }
+}
+
+impl JsonSchema for Person {
+    fn schema_name() -> String {
+        "Person".into()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        PersonValue::json_schema(gen)
+    }
+}
+
+impl JsonSchema for RepositoryField {
+    fn schema_name() -> String {
+        "RepositoryField".into()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        RepositoryValue::json_schema(gen)
+    }
+}
+
+#[cfg(feature = "protocols")]
+mod protocols {
+    use super::string_schema;
+    use crate::protocols::*;
+    use schemars::gen::SchemaGenerator;
+    use schemars::schema::Schema;
+    use schemars::JsonSchema;
+
+    impl JsonSchema for PackageManagerField {
+        fn schema_name() -> String {
+            "PackageManagerField".into()
+        }
+
+        fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+            string_schema(
+                "A package manager and exact version, with an optional hash, for example `pnpm@9.1.0+sha256.abc`.",
+                Some(r"^(bun|npm|pnpm|yarn)@[0-9]+\.[0-9]+\.[0-9]+(-[0-9A-Za-z.-]+)?(\+[0-9A-Za-z-]+\.[0-9A-Za-z]+)?$"),
+            )
+        }
+    }
+
+    impl JsonSchema for VersionProtocol {
+        fn schema_name() -> String {
+            "VersionProtocol".into()
+        }
+
+        // Built from the same patterns as the parser, while versions and
+        // ranges are only loosely matched.
+        fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+            let pattern = [
+                // *, or an empty string
+                r"^\*?$",
+                PROTOCOL_PATTERN.as_str(),
+                GITHUB_PATTERN,
+                r"^[ \^~<>=vV0-9xX*|.+-][ \^~<>=vV0-9A-Za-z*|.+-]*$",
+                TAG_PATTERN,
+            ]
+            .join("|");
+
+            string_schema(
+                "A version, range, tag, URL, GitHub shorthand, or protocol (`npm:`, `workspace:`, `file:`, etc).",
+                Some(&pattern),
+            )
+        }
+    }
+
+    impl JsonSchema for WorkspaceProtocol {
+        fn schema_name() -> String {
+            "WorkspaceProtocol".into()
+        }
+
+        fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+            string_schema(
+                "A workspace reference (without the `workspace:` prefix), for example `*`, `^`, `~1.2.3`, `alias@*`, or `../path`.",
+                Some(r"^([^@]+@)?(\*|[\^~]([0-9]+\.[0-9]+\.[0-9]+[^ ]*)?|[./].*|[0-9]+\.[0-9]+\.[0-9]+[^ ]*)$"),
+            )
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use serde::Deserialize;
+
+// https://webpack.js.org/guides/tree-shaking/#mark-the-file-as-side-effect-free
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(untagged)]
+pub enum SideEffectsField {
+    Bool(bool),
+    List(Vec<String>),
+}
@@ -0,0 +1,91 @@
+use crate::package_json::PackageJson;
+use crate::side_effects::SideEffectsField;
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::path::Path;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+#[cfg_attr(feature = "miette", derive(miette::Diagnostic))]
+pub enum SideEffectsError {
+    #[error("Invalid `sideEffects` glob `{glob}`.")]
+    #[cfg_attr(
+        feature = "miette",
+        diagnostic(code(package_json::side_effects::invalid_glob))
+    )]
+    InvalidGlob {
+        glob: String,
+        #[source]
+        error: globset::Error,
+    },
+}
+
+#[derive(Clone, Debug)]
+pub enum SideEffectsMatcher {
+    Bool(bool),
+    Globs(GlobSet),
+}
+
+impl SideEffectsMatcher {
+    // Checks whether a file, relative to the package root, has side effects.
+    pub fn is_side_effectful<T: AsRef<Path>>(&self, path: T) -> bool {
+        match self {
+            Self::Bool(value) => *value,
+            Self::Globs(set) => {
+                let path = path.as_ref().to_string_lossy().replace('\\', "/");
+
+                set.is_match(path.trim_start_matches("./"))
+            }
+        }
+    }
+}
+
+impl SideEffectsField {
+    // Patterns are matched like webpack does, where patterns without a
+    // slash match in any directory, and `*` does not cross directories.
+    pub fn to_matcher(&self) -> Result<SideEffectsMatcher, SideEffectsError> {
+        let globs = match self {
+            Self::Bool(value) => return Ok(SideEffectsMatcher::Bool(*value)),
+            Self::List(globs) => globs,
+        };
+
+        let mut builder = GlobSetBuilder::new();
+
+        for glob in globs {
+            let pattern = glob.trim_start_matches("./");
+            let pattern = if pattern.contains('/') {
+                pattern.to_owned()
+            } else {
+                format!("**/{pattern}")
+            };
+
+            builder.add(
+                GlobBuilder::new(&pattern)
+                    .literal_separator(true)
+                    .build()
+                    .map_err(|error| SideEffectsError::InvalidGlob {
+                        glob: glob.to_owned(),
+                        error,
+                    })?,
+            );
+        }
+
+        builder
+            .build()
+            .map(SideEffectsMatcher::Globs)
+            .map_err(|error| SideEffectsError::InvalidGlob {
+                glob: globs.join(", "),
+                error,
+            })
+    }
+}
+
+impl PackageJson {
+    // Checks whether a file, relative to the package root, has side effects.
+    // Files are assumed to have side effects when the field is not defined.
+    pub fn has_side_effects<T: AsRef<Path>>(&self, path: T) -> Result<bool, SideEffectsError> {
+        match &self.side_effects {
+            Some(field) => Ok(field.to_matcher()?.is_side_effectful(path)),
+            None => Ok(true),
+        }
+    }
+}
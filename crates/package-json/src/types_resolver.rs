@@ -0,0 +1,184 @@
+// Based on TypeScript's module resolution for declaration files.
+// https://www.typescriptlang.org/docs/handbook/modules/reference.html#packagejson-typesversions
+
+use crate::import_export::*;
+use crate::package_json::PackageJson;
+use crate::range::Range;
+use crate::FxIndexMap;
+use semver::Version;
+use std::path::{Path, PathBuf};
+
+// TypeScript version range -> path pattern -> replacements
+pub type TypesVersionsMap = FxIndexMap<String, FxIndexMap<String, Vec<String>>>;
+
+// Converts a JavaScript or TypeScript source path to the
+// declaration file that TypeScript would look for.
+pub fn to_declaration_path<T: AsRef<Path>>(path: T) -> PathBuf {
+    let path = path.as_ref();
+    let value = path.to_string_lossy();
+
+    if [".d.ts", ".d.mts", ".d.cts"]
+        .iter()
+        .any(|ext| value.ends_with(ext))
+    {
+        return path.to_path_buf();
+    }
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("js" | "jsx" | "ts" | "tsx") => path.with_extension("d.ts"),
+        Some("mjs" | "mts") => path.with_extension("d.mts"),
+        Some("cjs" | "cts") => path.with_extension("d.cts"),
+        _ => PathBuf::from(format!("{value}.d.ts")),
+    }
+}
+
+// Collects `types@<range>` conditions that match the TypeScript version.
+fn collect_versioned_conditions(
+    field: &ImportExportField,
+    ts_version: &Version,
+    conditions: &mut Vec<String>,
+) {
+    match field {
+        ImportExportField::Array(list) => {
+            for item in list {
+                collect_versioned_conditions(item, ts_version, conditions);
+            }
+        }
+        ImportExportField::Map(map) => {
+            for (key, value) in map {
+                let key = key.to_string();
+
+                if let Some(range) = key.strip_prefix("types@") {
+                    if Range::parse(range).is_ok_and(|range| range.satisfies(ts_version))
+                        && !conditions.contains(&key)
+                    {
+                        conditions.push(key);
+                    }
+                }
+
+                collect_versioned_conditions(value, ts_version, conditions);
+            }
+        }
+        _ => {}
+    };
+}
+
+// Like `paths` in tsconfig, exact matches take precedence,
+// and then the pattern with the longest prefix.
+fn match_path_pattern<'a>(
+    paths: &'a FxIndexMap<String, Vec<String>>,
+    path: &'a str,
+) -> Option<(&'a Vec<String>, &'a str)> {
+    if let Some(targets) = paths.get(path) {
+        return Some((targets, ""));
+    }
+
+    let mut matched: Option<(&Vec<String>, &str, usize)> = None;
+
+    for (pattern, targets) in paths {
+        let Some((prefix, suffix)) = pattern.split_once('*') else {
+            continue;
+        };
+
+        if path.len() >= prefix.len() + suffix.len()
+            && path.starts_with(prefix)
+            && path.ends_with(suffix)
+            && matched.map_or(true, |(_, _, len)| prefix.len() > len)
+        {
+            matched = Some((
+                targets,
+                &path[prefix.len()..path.len() - suffix.len()],
+                prefix.len(),
+            ));
+        }
+    }
+
+    matched.map(|(targets, star, _)| (targets, star))
+}
+
+impl PackageJson {
+    // Returns the `typesVersions` path mapping that applies to the TypeScript version.
+    pub fn get_types_versions_paths(
+        &self,
+        ts_version: &Version,
+    ) -> Option<&FxIndexMap<String, Vec<String>>> {
+        self.types_versions
+            .as_ref()?
+            .iter()
+            .find(|(range, _)| Range::parse(range).is_ok_and(|range| range.satisfies(ts_version)))
+            .map(|(_, paths)| paths)
+    }
+
+    fn apply_types_versions(&self, path: &str, ts_version: &Version) -> Option<String> {
+        let paths = self.get_types_versions_paths(ts_version)?;
+        let (targets, star) = match_path_pattern(paths, path)?;
+
+        targets.first().map(|target| {
+            target
+                .replace('*', star)
+                .trim_start_matches("./")
+                .to_owned()
+        })
+    }
+
+    // Returns the declaration file that TypeScript would pick for the subpath
+    // (`.` for the package root), relative to the package root. The `types`
+    // condition (and matching `types@<range>` conditions) are added to the
+    // provided conditions when resolving `exports`. When `exports` is not
+    // defined, `typesVersions`, `types`, `typings`, and `main` are used instead.
+    //
+    // File existence is not checked, as TypeScript would probe the file system.
+    // Subpaths outside of the package, like `../x`, return `None`.
+    pub fn resolve_types(
+        &self,
+        subpath: &str,
+        ts_version: &Version,
+        conditions: &[&str],
+    ) -> Option<PathBuf> {
+        if let Some(exports) = &self.exports {
+            let mut versioned = vec![];
+            collect_versioned_conditions(exports, ts_version, &mut versioned);
+
+            let mut all_conditions = vec!["types"];
+            all_conditions.extend(versioned.iter().map(|c| c.as_str()));
+            all_conditions.extend(conditions);
+
+            return exports
+                .resolve_export(subpath, &all_conditions)
+                .ok()
+                .map(|resolved| to_declaration_path(resolved.path));
+        }
+
+        let subpath = match subpath {
+            "." | "./" => "",
+            _ => subpath.strip_prefix("./").unwrap_or(subpath),
+        };
+
+        // Subpaths can't point outside of the package
+        if subpath == ".." || subpath.starts_with("../") {
+            return None;
+        }
+
+        if subpath.is_empty() {
+            let types = self
+                .types
+                .as_ref()
+                .or(self.typings.as_ref())
+                .map(to_declaration_path)
+                .or_else(|| self.main.as_ref().map(to_declaration_path))
+                .unwrap_or_else(|| PathBuf::from("index.d.ts"));
+            let types = types.to_string_lossy();
+            let types = types.trim_start_matches("./");
+
+            return Some(PathBuf::from(
+                self.apply_types_versions(types, ts_version)
+                    .unwrap_or_else(|| types.to_owned()),
+            ));
+        }
+
+        Some(to_declaration_path(
+            self.apply_types_versions(subpath, ts_version)
+                .unwrap_or_else(|| subpath.to_owned()),
+        ))
+    }
+}
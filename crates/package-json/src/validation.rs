@@ -0,0 +1,543 @@
+use crate::json_spans::*;
+use crate::package_json::PackageJson;
+use crate::protocols::VersionProtocol;
+use rustc_hash::FxHashMap;
+use serde_json::{Map, Value};
+use std::fmt;
+use std::str::FromStr;
+
+const DEPENDENCY_FIELDS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValidationSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValidationRule {
+    DuplicateDependency,
+    InvalidDependencyVersion,
+    InvalidExportTarget,
+    InvalidField,
+    InvalidJson,
+    InvalidName,
+    LegacyName,
+    MixedExportKeys,
+    PeerWithoutDevDependency,
+}
+
+impl ValidationRule {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::DuplicateDependency => "package_json::validate::duplicate_dependency",
+            Self::InvalidDependencyVersion => "package_json::validate::invalid_dependency_version",
+            Self::InvalidExportTarget => "package_json::validate::invalid_export_target",
+            Self::InvalidField => "package_json::validate::invalid_field",
+            Self::InvalidJson => "package_json::validate::invalid_json",
+            Self::InvalidName => "package_json::validate::invalid_name",
+            Self::LegacyName => "package_json::validate::legacy_name",
+            Self::MixedExportKeys => "package_json::validate::mixed_export_keys",
+            Self::PeerWithoutDevDependency => "package_json::validate::peer_without_dev_dependency",
+        }
+    }
+
+    pub fn severity(&self) -> ValidationSeverity {
+        match self {
+            Self::DuplicateDependency | Self::LegacyName | Self::PeerWithoutDevDependency => {
+                ValidationSeverity::Warning
+            }
+            _ => ValidationSeverity::Error,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidationDiagnostic {
+    pub message: String,
+
+    // JSON pointer to the offending value, like `/dependencies/foo`.
+    pub pointer: String,
+
+    pub rule: ValidationRule,
+    pub severity: ValidationSeverity,
+
+    // Byte offset and length within the source.
+    pub span: Option<(usize, usize)>,
+}
+
+impl fmt::Display for ValidationDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+impl std::error::Error for ValidationDiagnostic {}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ValidationReport {
+    pub diagnostics: Vec<ValidationDiagnostic>,
+
+    // The leniently parsed manifest, without invalid dependencies and fields,
+    // or `None` if the content is not a JSON object.
+    pub manifest: Option<PackageJson>,
+
+    pub source: String,
+}
+
+impl ValidationReport {
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics
+            .iter()
+            .any(|diag| diag.severity == ValidationSeverity::Error)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+}
+
+impl fmt::Display for ValidationReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Found {} problem(s) in package.json.",
+            self.diagnostics.len()
+        )
+    }
+}
+
+impl std::error::Error for ValidationReport {}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for ValidationDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new(self.rule.code()))
+    }
+
+    fn severity(&self) -> Option<miette::Severity> {
+        Some(match self.severity {
+            ValidationSeverity::Error => miette::Severity::Error,
+            ValidationSeverity::Warning => miette::Severity::Warning,
+        })
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = miette::LabeledSpan> + '_>> {
+        let span = self.span?;
+
+        Some(Box::new(std::iter::once(
+            miette::LabeledSpan::new_with_span(None, span),
+        )))
+    }
+}
+
+#[cfg(feature = "miette")]
+impl miette::Diagnostic for ValidationReport {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        Some(Box::new("package_json::validate"))
+    }
+
+    fn source_code(&self) -> Option<&dyn miette::SourceCode> {
+        Some(&self.source)
+    }
+
+    fn related<'a>(&'a self) -> Option<Box<dyn Iterator<Item = &'a dyn miette::Diagnostic> + 'a>> {
+        Some(Box::new(
+            self.diagnostics
+                .iter()
+                .map(|diag| diag as &dyn miette::Diagnostic),
+        ))
+    }
+}
+
+struct Validator {
+    diagnostics: Vec<ValidationDiagnostic>,
+    invalid_dependencies: Vec<(String, String)>,
+    spans: FxHashMap<String, JsonSpan>,
+}
+
+impl Validator {
+    fn report(&mut self, rule: ValidationRule, pointer: String, message: String) {
+        self.diagnostics.push(ValidationDiagnostic {
+            message,
+            rule,
+            severity: rule.severity(),
+            span: self.spans.get(&pointer).copied(),
+            pointer,
+        });
+    }
+
+    fn validate_name(&mut self, name: &Value) {
+        let pointer = "/name".to_owned();
+
+        match name.as_str() {
+            Some(name) => {
+                let result = validate_package_name(name);
+
+                for message in result.errors {
+                    self.report(ValidationRule::InvalidName, pointer.clone(), message);
+                }
+
+                for message in result.warnings {
+                    self.report(ValidationRule::LegacyName, pointer.clone(), message);
+                }
+            }
+            None => {
+                self.report(
+                    ValidationRule::InvalidName,
+                    pointer,
+                    "Name must be a string.".into(),
+                );
+            }
+        };
+    }
+
+    fn validate_dependencies(&mut self, field: &str, deps: &Map<String, Value>) {
+        for (name, version) in deps {
+            let pointer = format!("/{field}/{}", escape_pointer_token(name));
+
+            let Some(version) = version.as_str() else {
+                self.report(
+                    ValidationRule::InvalidDependencyVersion,
+                    pointer,
+                    format!("Version for `{name}` in `{field}` must be a string."),
+                );
+                self.invalid_dependencies
+                    .push((field.to_owned(), name.to_owned()));
+
+                continue;
+            };
+
+            if let Err(error) = VersionProtocol::from_str(version) {
+                self.report(
+                    ValidationRule::InvalidDependencyVersion,
+                    pointer,
+                    format!("Invalid version `{version}` for `{name}` in `{field}`: {error}"),
+                );
+                self.invalid_dependencies
+                    .push((field.to_owned(), name.to_owned()));
+            }
+        }
+    }
+
+    fn validate_exports(&mut self, value: &Value, pointer: String) {
+        match value {
+            Value::String(target) => {
+                if !target.starts_with("./") {
+                    self.report(
+                        ValidationRule::InvalidExportTarget,
+                        pointer,
+                        format!("Export target `{target}` must start with `./`."),
+                    );
+                }
+            }
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.validate_exports(item, format!("{pointer}/{index}"));
+                }
+            }
+            Value::Object(map) => {
+                let subpaths = map.keys().filter(|key| key.starts_with('.')).count();
+
+                if subpaths > 0 && subpaths != map.len() {
+                    self.report(
+                        ValidationRule::MixedExportKeys,
+                        pointer.clone(),
+                        "Exports cannot mix subpath keys (starting with `.`) and condition keys in the same object.".into(),
+                    );
+                }
+
+                for (key, item) in map {
+                    self.validate_exports(item, format!("{pointer}/{}", escape_pointer_token(key)));
+                }
+            }
+            _ => {}
+        };
+    }
+
+    // Drops invalid dependencies, and fields that fail to deserialize,
+    // so that the rest of the manifest is still available.
+    fn parse_manifest(&mut self, mut data: Map<String, Value>) -> Option<PackageJson> {
+        for (field, name) in &self.invalid_dependencies {
+            if let Some(deps) = data.get_mut(field).and_then(|deps| deps.as_object_mut()) {
+                deps.remove(name);
+            }
+        }
+
+        data.retain(|key, value| {
+            let field = Map::from_iter([(key.to_owned(), value.to_owned())]);
+
+            if serde_json::from_value::<PackageJson>(Value::Object(field)).is_ok() {
+                return true;
+            }
+
+            let pointer = format!("/{}", escape_pointer_token(key));
+
+            if !self.diagnostics.iter().any(|diag| diag.pointer == pointer) {
+                self.report(
+                    ValidationRule::InvalidField,
+                    pointer,
+                    format!("Field `{key}` has an invalid value and was ignored."),
+                );
+            }
+
+            false
+        });
+
+        serde_json::from_value(Value::Object(data)).ok()
+    }
+}
+
+const BUILTIN_MODULES: [&str; 42] = [
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "diagnostics_channel",
+    "dns",
+    "domain",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "sys",
+    "timers",
+    "tls",
+    "trace_events",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "wasi",
+    "worker_threads",
+    "zlib",
+];
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PackageNameValidation {
+    // Problems that make the name invalid for any package.
+    pub errors: Vec<String>,
+
+    // Problems that only make the name invalid for new packages,
+    // as existing packages may have been published before the rule.
+    pub warnings: Vec<String>,
+}
+
+impl PackageNameValidation {
+    pub fn is_valid_for_new_packages(&self) -> bool {
+        self.errors.is_empty() && self.warnings.is_empty()
+    }
+
+    pub fn is_valid_for_old_packages(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+// https://github.com/npm/validate-npm-package-name
+pub fn validate_package_name(name: &str) -> PackageNameValidation {
+    let mut result = PackageNameValidation::default();
+    let errors = &mut result.errors;
+    let warnings = &mut result.warnings;
+
+    if name.is_empty() {
+        errors.push("Name cannot be empty.".into());
+    }
+
+    if name.starts_with('.') {
+        errors.push("Name cannot start with a period.".into());
+    }
+
+    if name.starts_with('_') {
+        errors.push("Name cannot start with an underscore.".into());
+    }
+
+    if name.trim() != name {
+        errors.push("Name cannot contain leading or trailing spaces.".into());
+    }
+
+    if name.eq_ignore_ascii_case("node_modules") || name.eq_ignore_ascii_case("favicon.ico") {
+        errors.push(format!("Name `{name}` is not allowed."));
+    }
+
+    if BUILTIN_MODULES.contains(&name.to_lowercase().as_str()) {
+        warnings.push(format!("Name `{name}` is a Node.js core module name."));
+    }
+
+    if name.len() > 214 {
+        warnings.push("Name cannot be longer than 214 characters.".into());
+    }
+
+    if name.to_lowercase() != name {
+        warnings.push("Name cannot contain uppercase letters.".into());
+    }
+
+    if name
+        .rsplit('/')
+        .next()
+        .is_some_and(|last| last.contains(['~', '\'', '!', '(', ')', '*']))
+    {
+        warnings.push("Name cannot contain special characters (\"~\'!()*\").".into());
+    }
+
+    // Characters that are not escaped by `encodeURIComponent`
+    let is_url_safe = |part: &str| {
+        part.chars().all(|c| {
+            c.is_ascii_alphanumeric()
+                || matches!(c, '-' | '_' | '.' | '!' | '~' | '*' | '\'' | '(' | ')')
+        })
+    };
+
+    let is_valid_url = match name.strip_prefix('@') {
+        Some(scoped) => match scoped.split_once('/') {
+            Some((scope, pkg)) if !scope.is_empty() && !pkg.is_empty() && !pkg.contains('/') => {
+                is_url_safe(scope) && is_url_safe(pkg)
+            }
+            _ => {
+                errors.push("Scoped name must be in the format `@scope/name`.".into());
+                true
+            }
+        },
+        None => is_url_safe(name),
+    };
+
+    if !is_valid_url {
+        errors.push("Name can only contain URL-friendly characters.".into());
+    }
+
+    result
+}
+
+// serde reports 1-based lines and columns.
+fn to_offset(content: &str, line: usize, column: usize) -> usize {
+    let line_offset = content
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(|line| line.len())
+        .sum::<usize>();
+
+    (line_offset + column.saturating_sub(1)).min(content.len())
+}
+
+impl PackageJson {
+    // Validates the raw content of a `package.json`, collecting as many
+    // problems as possible, instead of failing on the first.
+    pub fn validate<T: AsRef<str>>(content: T) -> ValidationReport {
+        let content = content.as_ref();
+        let mut report = ValidationReport {
+            diagnostics: vec![],
+            manifest: None,
+            source: content.to_owned(),
+        };
+
+        let data = match serde_json::from_str::<Value>(content) {
+            Ok(Value::Object(data)) => data,
+            Ok(_) => {
+                report.diagnostics.push(ValidationDiagnostic {
+                    message: "Expected an object at the root of package.json.".into(),
+                    pointer: String::new(),
+                    rule: ValidationRule::InvalidJson,
+                    severity: ValidationSeverity::Error,
+                    span: None,
+                });
+
+                return report;
+            }
+            Err(error) => {
+                report.diagnostics.push(ValidationDiagnostic {
+                    message: error.to_string(),
+                    pointer: String::new(),
+                    rule: ValidationRule::InvalidJson,
+                    severity: ValidationSeverity::Error,
+                    span: Some((to_offset(content, error.line(), error.column()), 0)),
+                });
+
+                return report;
+            }
+        };
+
+        let mut validator = Validator {
+            diagnostics: vec![],
+            invalid_dependencies: vec![],
+            spans: index_json_spans(content),
+        };
+
+        if let Some(name) = data.get("name") {
+            validator.validate_name(name);
+        }
+
+        let get_deps = |field: &str| data.get(field).and_then(|deps| deps.as_object());
+
+        for field in DEPENDENCY_FIELDS {
+            if let Some(deps) = get_deps(field) {
+                validator.validate_dependencies(field, deps);
+            }
+        }
+
+        let empty = Map::new();
+        let prod_deps = get_deps("dependencies").unwrap_or(&empty);
+        let dev_deps = get_deps("devDependencies").unwrap_or(&empty);
+
+        for name in dev_deps.keys() {
+            if prod_deps.contains_key(name) {
+                validator.report(
+                    ValidationRule::DuplicateDependency,
+                    format!("/devDependencies/{}", escape_pointer_token(name)),
+                    format!("Dependency `{name}` is listed in both `dependencies` and `devDependencies`."),
+                );
+            }
+        }
+
+        if let Some(peer_deps) = get_deps("peerDependencies") {
+            for name in peer_deps.keys() {
+                if !dev_deps.contains_key(name) {
+                    validator.report(
+                        ValidationRule::PeerWithoutDevDependency,
+                        format!("/peerDependencies/{}", escape_pointer_token(name)),
+                        format!(
+                            "Peer dependency `{name}` should also be listed in `devDependencies`."
+                        ),
+                    );
+                }
+            }
+        }
+
+        if let Some(exports) = data.get("exports") {
+            validator.validate_exports(exports, "/exports".into());
+        }
+
+        report.manifest = validator.parse_manifest(data);
+
+        // Fields are visited by name, so report in source order instead
+        validator
+            .diagnostics
+            .sort_by_key(|diag| diag.span.map(|(offset, _)| offset));
+
+        report.diagnostics = validator.diagnostics;
+        report
+    }
+}
@@ -0,0 +1,136 @@
+use nodejs_package_json::{BrowserMapping, PackageJson};
+use std::path::PathBuf;
+
+const EXTENSIONS: &[&str] = &[".js", ".json"];
+
+fn parse(content: &str) -> PackageJson {
+    serde_json::from_str(content).unwrap()
+}
+
+fn file(path: &str) -> Option<BrowserMapping> {
+    Some(BrowserMapping::File(PathBuf::from(path)))
+}
+
+mod string {
+    use super::*;
+
+    #[test]
+    fn replaces_main() {
+        let pkg = parse(r#"{ "main": "./lib/index.js", "browser": "./browser.js" }"#);
+
+        assert_eq!(pkg.resolve_browser(".", EXTENSIONS), file("browser.js"));
+        assert_eq!(
+            pkg.resolve_browser("./lib/index.js", EXTENSIONS),
+            file("browser.js")
+        );
+        assert_eq!(
+            pkg.resolve_browser("./lib/index", EXTENSIONS),
+            file("browser.js")
+        );
+        assert_eq!(pkg.resolve_browser("./lib", EXTENSIONS), file("browser.js"));
+    }
+
+    #[test]
+    fn doesnt_replace_other_files() {
+        let pkg = parse(r#"{ "main": "./lib/index.js", "browser": "./browser.js" }"#);
+
+        assert_eq!(pkg.resolve_browser("./lib/util.js", EXTENSIONS), None);
+        assert_eq!(pkg.resolve_browser("fs", EXTENSIONS), None);
+    }
+}
+
+mod map {
+    use super::*;
+
+    #[test]
+    fn replaces_files() {
+        let pkg = parse(
+            r#"{
+                "main": "./lib/index.js",
+                "browser": { "./lib/server.js": "./lib/client.js", "lib/node.js": "./lib/web.js" }
+            }"#,
+        );
+
+        assert_eq!(
+            pkg.resolve_browser("./lib/server.js", EXTENSIONS),
+            file("lib/client.js")
+        );
+        assert_eq!(
+            pkg.resolve_browser("./lib/../lib/server.js", EXTENSIONS),
+            file("lib/client.js")
+        );
+        assert_eq!(
+            pkg.resolve_browser("./lib/node.js", EXTENSIONS),
+            file("lib/web.js")
+        );
+        assert_eq!(pkg.resolve_browser("./lib/other.js", EXTENSIONS), None);
+    }
+
+    #[test]
+    fn matches_without_extensions() {
+        let pkg = parse(
+            r#"{
+                "main": "./lib/index.js",
+                "browser": { "./lib/server.js": "./lib/client.js", "./lib/node": "./lib/web.js", "./lib/dir/index.js": false }
+            }"#,
+        );
+
+        assert_eq!(
+            pkg.resolve_browser("./lib/server", EXTENSIONS),
+            file("lib/client.js")
+        );
+        assert_eq!(
+            pkg.resolve_browser("./lib/node.js", EXTENSIONS),
+            file("lib/web.js")
+        );
+        assert_eq!(
+            pkg.resolve_browser("./lib/dir", EXTENSIONS),
+            Some(BrowserMapping::Disabled)
+        );
+        assert_eq!(pkg.resolve_browser("./lib/server", &[]), None);
+    }
+
+    #[test]
+    fn replaces_modules() {
+        let pkg = parse(
+            r#"{
+                "main": "./lib/index.js",
+                "browser": { "fs": false, "buffer": "buffer-polyfill", "module/sub": "./shim.js", "invalid": 1 }
+            }"#,
+        );
+
+        assert_eq!(
+            pkg.resolve_browser("fs", EXTENSIONS),
+            Some(BrowserMapping::Disabled)
+        );
+        assert_eq!(
+            pkg.resolve_browser("buffer", EXTENSIONS),
+            Some(BrowserMapping::Module("buffer-polyfill".into()))
+        );
+        assert_eq!(
+            pkg.resolve_browser("module/sub", EXTENSIONS),
+            file("shim.js")
+        );
+        assert_eq!(pkg.resolve_browser("module", EXTENSIONS), None);
+        assert_eq!(pkg.resolve_browser("invalid", EXTENSIONS), None);
+    }
+
+    #[test]
+    fn replaces_main() {
+        let pkg = parse(
+            r#"{
+                "main": "./lib/index.js",
+                "browser": { "./lib/index.js": "./browser.js" }
+            }"#,
+        );
+
+        assert_eq!(pkg.resolve_browser(".", EXTENSIONS), file("browser.js"));
+    }
+}
+
+#[test]
+fn returns_none_without_field() {
+    let pkg = PackageJson::default();
+
+    assert_eq!(pkg.resolve_browser(".", EXTENSIONS), None);
+}
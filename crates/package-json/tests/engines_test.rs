@@ -0,0 +1,59 @@
+use nodejs_package_json::{EngineCheck, PackageJson, Range, RuntimeVersions};
+use semver::Version;
+
+fn parse(content: &str) -> PackageJson {
+    serde_json::from_str(content).unwrap()
+}
+
+#[test]
+fn checks_each_engine() {
+    let pkg = parse(
+        r#"{
+            "engines": {
+                "node": "^18.18 || >=20",
+                "npm": ">=10.5",
+                "pnpm": "8.x",
+                "vscode": "latest"
+            }
+        }"#,
+    );
+    let runtimes = RuntimeVersions::new()
+        .with("node", Version::new(18, 19, 0))
+        .with("npm", Version::new(10, 2, 0))
+        .with("pnpm", Version::new(8, 15, 4))
+        .with("vscode", Version::new(1, 90, 0));
+
+    let checks = pkg.check_engines(&runtimes);
+
+    assert_eq!(checks.get("node").unwrap(), &EngineCheck::Satisfied);
+    assert_eq!(
+        checks.get("npm").unwrap(),
+        &EngineCheck::Violated {
+            range: Range::parse(">=10.5").unwrap(),
+            version: Version::new(10, 2, 0),
+        }
+    );
+    assert_eq!(checks.get("pnpm").unwrap(), &EngineCheck::Satisfied);
+    assert_eq!(
+        checks.get("vscode").unwrap(),
+        &EngineCheck::Unparseable("latest".into())
+    );
+}
+
+#[test]
+fn skips_engines_without_runtime() {
+    let pkg = parse(r#"{ "engines": { "node": ">=20", "bun": ">=1" } }"#);
+    let checks = pkg.check_engines(&RuntimeVersions::new().with("node", Version::new(22, 0, 0)));
+
+    assert_eq!(checks.len(), 1);
+    assert_eq!(checks.get("node").unwrap(), &EngineCheck::Satisfied);
+}
+
+#[test]
+fn returns_nothing_without_engines() {
+    let pkg = parse(r#"{ "name": "pkg" }"#);
+
+    assert!(pkg
+        .check_engines(&RuntimeVersions::new().with("node", Version::new(22, 0, 0)))
+        .is_empty());
+}
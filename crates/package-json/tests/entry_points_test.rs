@@ -0,0 +1,191 @@
+use nodejs_package_json::{EntryPoint, ImportExportError, ImportExportKey, PackageJson};
+use starbase_sandbox::create_empty_sandbox;
+use std::path::{Path, PathBuf};
+
+fn parse(content: &str) -> PackageJson {
+    serde_json::from_str(content).unwrap()
+}
+
+fn entry(subpath: &str, conditions: &[&str], key: &str, path: &str) -> EntryPoint {
+    EntryPoint {
+        conditions: conditions.iter().map(|c| c.to_string()).collect(),
+        key: ImportExportKey::from(key),
+        path: PathBuf::from(path),
+        subpath: subpath.into(),
+    }
+}
+
+mod exports {
+    use super::*;
+
+    #[test]
+    fn lists_string_sugar() {
+        let pkg = parse(r#"{ "exports": "./index.js" }"#);
+
+        assert_eq!(
+            pkg.get_entry_points(Path::new("/missing")).unwrap(),
+            [entry(".", &[], ".", "index.js")]
+        );
+    }
+
+    #[test]
+    fn lists_conditions_sugar() {
+        let pkg = parse(r#"{ "exports": { "import": "./index.mjs", "require": "./index.cjs" } }"#);
+
+        assert_eq!(
+            pkg.get_entry_points(Path::new("/missing")).unwrap(),
+            [
+                entry(".", &["import"], ".", "index.mjs"),
+                entry(".", &["require"], ".", "index.cjs"),
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_nested_conditions() {
+        let pkg = parse(
+            r#"{
+                "exports": {
+                    ".": {
+                        "node": { "import": "./node.mjs", "default": "./node.cjs" },
+                        "default": "./index.js",
+                        "browser": "./browser.js"
+                    },
+                    "./utils": ["./utils.js", "./fallback.js"],
+                    "./package.json": "./package.json"
+                }
+            }"#,
+        );
+
+        assert_eq!(
+            pkg.get_entry_points(Path::new("/missing")).unwrap(),
+            [
+                entry(".", &["node", "import"], ".", "node.mjs"),
+                entry(".", &["node", "default"], ".", "node.cjs"),
+                entry(".", &["default"], ".", "index.js"),
+                entry("./utils", &[], "./utils", "utils.js"),
+                entry("./package.json", &[], "./package.json", "package.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn omits_excluded_and_shadowed() {
+        let pkg = parse(
+            r#"{
+                "exports": {
+                    ".": { "import": "./index.mjs", "node": { "import": "./node.mjs" } },
+                    "./internal": null
+                }
+            }"#,
+        );
+
+        assert_eq!(
+            pkg.get_entry_points(Path::new("/missing")).unwrap(),
+            [entry(".", &["import"], ".", "index.mjs")]
+        );
+    }
+
+    #[test]
+    fn expands_patterns() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("src/features/a.js", "");
+        sandbox.create_file("src/features/b.js", "");
+        sandbox.create_file("src/features/nested/c.js", "");
+        sandbox.create_file("src/features/types.d.ts", "");
+        sandbox.create_file("src/features/private/d.js", "");
+
+        let pkg = parse(
+            r#"{
+                "exports": {
+                    "./features/*.js": {
+                        "types": "./src/features/*.d.ts",
+                        "default": "./src/features/*.js"
+                    },
+                    "./features/private/*": null
+                }
+            }"#,
+        );
+
+        assert_eq!(
+            pkg.get_entry_points(sandbox.path()).unwrap(),
+            [
+                entry(
+                    "./features/types.js",
+                    &["types"],
+                    "./features/*.js",
+                    "src/features/types.d.ts"
+                ),
+                entry(
+                    "./features/a.js",
+                    &["default"],
+                    "./features/*.js",
+                    "src/features/a.js"
+                ),
+                entry(
+                    "./features/b.js",
+                    &["default"],
+                    "./features/*.js",
+                    "src/features/b.js"
+                ),
+                entry(
+                    "./features/nested/c.js",
+                    &["default"],
+                    "./features/*.js",
+                    "src/features/nested/c.js"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn errors_for_invalid_config() {
+        let pkg = parse(r#"{ "exports": { ".": "./index.js", "import": "./index.mjs" } }"#);
+
+        assert_eq!(
+            pkg.get_entry_points(Path::new("/missing")).unwrap_err(),
+            ImportExportError::InvalidConfig
+        );
+    }
+}
+
+mod legacy {
+    use super::*;
+
+    #[test]
+    fn lists_fields() {
+        let pkg = parse(
+            r#"{
+                "main": "./lib/index.js",
+                "module": "./esm/index.js",
+                "browser": "./browser.js",
+                "types": "./index.d.ts"
+            }"#,
+        );
+
+        assert_eq!(
+            pkg.get_entry_points(Path::new("/missing")).unwrap(),
+            [
+                entry(".", &[], ".", "lib/index.js"),
+                entry(".", &["module"], ".", "esm/index.js"),
+                entry(".", &["browser"], ".", "browser.js"),
+                entry(".", &["types"], ".", "index.d.ts"),
+            ]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_index() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("index.js", "");
+
+        assert_eq!(
+            parse("{}").get_entry_points(sandbox.path()).unwrap(),
+            [entry(".", &[], ".", "index.js")]
+        );
+        assert_eq!(
+            parse("{}").get_entry_points(Path::new("/missing")).unwrap(),
+            []
+        );
+    }
+}
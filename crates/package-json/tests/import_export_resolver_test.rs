@@ -0,0 +1,333 @@
+use nodejs_package_json::{
+    ImportExportError, ImportExportKey, ImportTarget, PackageJson, ResolvedExport, ResolvedImport,
+};
+use std::path::PathBuf;
+
+fn parse(content: &str) -> PackageJson {
+    serde_json::from_str(content).unwrap()
+}
+
+mod exports {
+    use super::*;
+
+    #[test]
+    fn resolves_string_sugar() {
+        let pkg = parse(r#"{ "exports": "./index.js" }"#);
+
+        assert_eq!(
+            pkg.resolve_export(".", &[]).unwrap(),
+            ResolvedExport {
+                conditions: vec![],
+                key: ImportExportKey::Main,
+                path: PathBuf::from("index.js"),
+            }
+        );
+        assert_eq!(
+            pkg.resolve_export("./sub", &[]).unwrap_err(),
+            ImportExportError::NotExported("./sub".into())
+        );
+    }
+
+    #[test]
+    fn resolves_conditions_sugar() {
+        let pkg = parse(r#"{ "exports": { "import": "./index.mjs", "require": "./index.cjs" } }"#);
+
+        assert_eq!(
+            pkg.resolve_export(".", &["require"]).unwrap(),
+            ResolvedExport {
+                conditions: vec!["require".into()],
+                key: ImportExportKey::Main,
+                path: PathBuf::from("index.cjs"),
+            }
+        );
+    }
+
+    #[test]
+    fn resolves_subpaths() {
+        let pkg = parse(r#"{ "exports": { ".": "./index.js", "./sub": "./lib/sub.js" } }"#);
+
+        assert_eq!(
+            pkg.resolve_export("", &[]).unwrap().path,
+            PathBuf::from("index.js")
+        );
+        assert_eq!(
+            pkg.resolve_export("./sub", &[]).unwrap().path,
+            PathBuf::from("lib/sub.js")
+        );
+        assert_eq!(
+            pkg.resolve_export("sub", &[]).unwrap().path,
+            PathBuf::from("lib/sub.js")
+        );
+    }
+
+    #[test]
+    fn follows_condition_order() {
+        let pkg = parse(
+            r#"{
+                "exports": { ".": { "types": "./index.d.ts", "import": "./index.mjs", "default": "./index.js" } }
+            }"#,
+        );
+
+        assert_eq!(
+            pkg.resolve_export(".", &["import", "types"]).unwrap(),
+            ResolvedExport {
+                conditions: vec!["types".into()],
+                key: ImportExportKey::Main,
+                path: PathBuf::from("index.d.ts"),
+            }
+        );
+        assert_eq!(
+            pkg.resolve_export(".", &["node"]).unwrap().path,
+            PathBuf::from("index.js")
+        );
+    }
+
+    #[test]
+    fn resolves_nested_conditions() {
+        let pkg = parse(
+            r#"{
+                "exports": { ".": { "node": { "import": "./node.mjs", "require": "./node.cjs" }, "default": "./browser.js" } }
+            }"#,
+        );
+
+        assert_eq!(
+            pkg.resolve_export(".", &["node", "require"]).unwrap(),
+            ResolvedExport {
+                conditions: vec!["node".into(), "require".into()],
+                key: ImportExportKey::Main,
+                path: PathBuf::from("node.cjs"),
+            }
+        );
+
+        // Falls through to default when no nested condition matches
+        assert_eq!(
+            pkg.resolve_export(".", &["node"]).unwrap(),
+            ResolvedExport {
+                conditions: vec!["default".into()],
+                key: ImportExportKey::Main,
+                path: PathBuf::from("browser.js"),
+            }
+        );
+    }
+
+    #[test]
+    fn resolves_patterns() {
+        let pkg = parse(
+            r#"{
+                "exports": { "./*": "./src/*.js", "./features/*.js": "./src/features/*.js", "./features/internal/*": null }
+            }"#,
+        );
+
+        assert_eq!(
+            pkg.resolve_export("./utils/string", &[]).unwrap(),
+            ResolvedExport {
+                conditions: vec![],
+                key: ImportExportKey::Pattern("/*".into()),
+                path: PathBuf::from("src/utils/string.js"),
+            }
+        );
+        assert_eq!(
+            pkg.resolve_export("./features/a.js", &[]).unwrap(),
+            ResolvedExport {
+                conditions: vec![],
+                key: ImportExportKey::Pattern("/features/*.js".into()),
+                path: PathBuf::from("src/features/a.js"),
+            }
+        );
+        assert_eq!(
+            pkg.resolve_export("./features/internal/a", &[])
+                .unwrap_err(),
+            ImportExportError::NotExported("./features/internal/a".into())
+        );
+    }
+
+    #[test]
+    fn prefers_exact_match_over_pattern() {
+        let pkg = parse(r#"{ "exports": { "./*": "./src/*.js", "./special": "./special.js" } }"#);
+
+        assert_eq!(
+            pkg.resolve_export("./special", &[]).unwrap().path,
+            PathBuf::from("special.js")
+        );
+    }
+
+    #[test]
+    fn uses_array_fallbacks() {
+        let pkg = parse(
+            r#"{
+                "exports": { ".": [{ "worker": "./worker.js" }, "invalid", "./index.js"] }
+            }"#,
+        );
+
+        assert_eq!(
+            pkg.resolve_export(".", &[]).unwrap().path,
+            PathBuf::from("index.js")
+        );
+        assert_eq!(
+            pkg.resolve_export(".", &["worker"]).unwrap(),
+            ResolvedExport {
+                conditions: vec!["worker".into()],
+                key: ImportExportKey::Main,
+                path: PathBuf::from("worker.js"),
+            }
+        );
+    }
+
+    #[test]
+    fn errors_for_null_target() {
+        let pkg = parse(r#"{ "exports": { ".": { "import": null, "default": "./index.js" } } }"#);
+
+        assert_eq!(
+            pkg.resolve_export(".", &["import"]).unwrap_err(),
+            ImportExportError::NotExported(".".into())
+        );
+    }
+
+    #[test]
+    fn errors_for_mixed_keys() {
+        let pkg = parse(r#"{ "exports": { ".": "./index.js", "import": "./index.mjs" } }"#);
+
+        assert_eq!(
+            pkg.resolve_export(".", &[]).unwrap_err(),
+            ImportExportError::InvalidConfig
+        );
+    }
+
+    #[test]
+    fn errors_for_invalid_target() {
+        let pkg = parse(
+            r#"{
+                "exports": { "./a": "index.js", "./b": "./../index.js", "./c": "./node_modules/dep/index.js" }
+            }"#,
+        );
+
+        for subpath in ["./a", "./b", "./c"] {
+            assert!(matches!(
+                pkg.resolve_export(subpath, &[]).unwrap_err(),
+                ImportExportError::InvalidTarget { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn errors_for_invalid_pattern_match() {
+        let pkg = parse(r#"{ "exports": { "./*": "./src/*" } }"#);
+
+        assert_eq!(
+            pkg.resolve_export("./../secret.js", &[]).unwrap_err(),
+            ImportExportError::InvalidSubpath("./../secret.js".into())
+        );
+    }
+
+    #[test]
+    fn errors_when_no_exports() {
+        let pkg = PackageJson::default();
+
+        assert_eq!(
+            pkg.resolve_export(".", &[]).unwrap_err(),
+            ImportExportError::NotExported(".".into())
+        );
+    }
+}
+
+mod imports {
+    use super::*;
+
+    #[test]
+    fn resolves_file() {
+        let pkg = parse(r##"{ "imports": { "#internal": "./src/internal.js" } }"##);
+
+        assert_eq!(
+            pkg.resolve_import("#internal", &[]).unwrap(),
+            ResolvedImport {
+                conditions: vec![],
+                key: ImportExportKey::Pattern("#internal".into()),
+                target: ImportTarget::File(PathBuf::from("src/internal.js")),
+            }
+        );
+    }
+
+    #[test]
+    fn resolves_patterns() {
+        let pkg = parse(r##"{ "imports": { "#internal/*.js": "./src/internal/*.js" } }"##);
+
+        assert_eq!(
+            pkg.resolve_import("#internal/foo/bar.js", &[]).unwrap(),
+            ResolvedImport {
+                conditions: vec![],
+                key: ImportExportKey::Pattern("#internal/*.js".into()),
+                target: ImportTarget::File(PathBuf::from("src/internal/foo/bar.js")),
+            }
+        );
+    }
+
+    #[test]
+    fn resolves_packages() {
+        let pkg = parse(
+            r##"{
+                "imports": { "#dep": { "node": "dep-node-native", "default": "./dep-polyfill.js" }, "#lodash/*": "lodash-es/*" }
+            }"##,
+        );
+
+        assert_eq!(
+            pkg.resolve_import("#dep", &["node", "import"]).unwrap(),
+            ResolvedImport {
+                conditions: vec!["node".into()],
+                key: ImportExportKey::Pattern("#dep".into()),
+                target: ImportTarget::Package("dep-node-native".into()),
+            }
+        );
+        assert_eq!(
+            pkg.resolve_import("#dep", &["browser"]).unwrap().target,
+            ImportTarget::File(PathBuf::from("dep-polyfill.js"))
+        );
+        assert_eq!(
+            pkg.resolve_import("#lodash/map", &[]).unwrap().target,
+            ImportTarget::Package("lodash-es/map".into())
+        );
+    }
+
+    #[test]
+    fn errors_for_invalid_specifier() {
+        let pkg = parse(r##"{ "imports": { "#internal": "./src/internal.js" } }"##);
+
+        for specifier in ["internal", "#", "#/internal"] {
+            assert_eq!(
+                pkg.resolve_import(specifier, &[]).unwrap_err(),
+                ImportExportError::InvalidSpecifier(specifier.into())
+            );
+        }
+    }
+
+    #[test]
+    fn errors_for_invalid_target() {
+        let pkg = parse(
+            r##"{
+                "imports": { "#parent": "../parent.js", "#abs": "/root.js", "#url": "https://domain.com/file.js" }
+            }"##,
+        );
+
+        for specifier in ["#parent", "#abs", "#url"] {
+            assert!(matches!(
+                pkg.resolve_import(specifier, &[]).unwrap_err(),
+                ImportExportError::InvalidTarget { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn errors_when_not_defined() {
+        let pkg = parse(r##"{ "imports": { "#internal": "./src/internal.js" } }"##);
+
+        assert_eq!(
+            pkg.resolve_import("#unknown", &[]).unwrap_err(),
+            ImportExportError::NotDefined("#unknown".into())
+        );
+        assert_eq!(
+            PackageJson::default()
+                .resolve_import("#internal", &[])
+                .unwrap_err(),
+            ImportExportError::NotDefined("#internal".into())
+        );
+    }
+}
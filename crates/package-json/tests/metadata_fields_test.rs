@@ -0,0 +1,295 @@
+use nodejs_package_json::{
+    BinField, BugsField, FundingField, LicenseField, PackageJson, Person, RepositoryField,
+};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+fn parse(content: &str) -> PackageJson {
+    serde_json::from_str(content).unwrap()
+}
+
+mod bin {
+    use super::*;
+
+    #[test]
+    fn supports_string() {
+        let pkg = parse(r#"{ "bin": "./cli.js" }"#);
+
+        assert_eq!(
+            pkg.bin.unwrap(),
+            BinField::String(PathBuf::from("./cli.js"))
+        );
+    }
+
+    #[test]
+    fn supports_map() {
+        let pkg = parse(r#"{ "bin": { "a": "./a.js", "b": "./b.js" } }"#);
+
+        assert_eq!(
+            pkg.bin.unwrap(),
+            BinField::Map(
+                [
+                    ("a".into(), PathBuf::from("./a.js")),
+                    ("b".into(), PathBuf::from("./b.js"))
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+    }
+}
+
+mod license {
+    use super::*;
+
+    #[test]
+    fn supports_spdx() {
+        let pkg = parse(r#"{ "license": "(MIT OR Apache-2.0)" }"#);
+
+        assert_eq!(
+            pkg.license.unwrap(),
+            LicenseField::Spdx("(MIT OR Apache-2.0)".into())
+        );
+    }
+
+    #[test]
+    fn supports_file() {
+        let pkg = parse(r#"{ "license": "SEE LICENSE IN LICENSE.txt" }"#);
+
+        assert_eq!(
+            pkg.license.unwrap(),
+            LicenseField::File(PathBuf::from("LICENSE.txt"))
+        );
+    }
+
+    #[test]
+    fn supports_unlicensed() {
+        let pkg = parse(r#"{ "license": "UNLICENSED" }"#);
+
+        assert_eq!(pkg.license.unwrap(), LicenseField::Unlicensed);
+    }
+
+    #[test]
+    fn supports_legacy_object() {
+        let pkg = parse(
+            r#"{ "license": { "type": "ISC", "url": "https://opensource.org/licenses/ISC" } }"#,
+        );
+
+        assert_eq!(
+            pkg.license.unwrap(),
+            LicenseField::Legacy {
+                type_of: "ISC".into(),
+                url: Some("https://opensource.org/licenses/ISC".into())
+            }
+        );
+    }
+}
+
+mod person {
+    use super::*;
+
+    #[test]
+    fn parses_string() {
+        assert_eq!(
+            Person::from_str("Barney Rubble <b@rubble.com> (http://barnyrubble.tumblr.com/)")
+                .unwrap(),
+            Person {
+                name: "Barney Rubble".into(),
+                email: Some("b@rubble.com".into()),
+                url: Some("http://barnyrubble.tumblr.com/".into()),
+            }
+        );
+        assert_eq!(
+            Person::from_str("Barney Rubble (http://barnyrubble.tumblr.com/)").unwrap(),
+            Person {
+                name: "Barney Rubble".into(),
+                email: None,
+                url: Some("http://barnyrubble.tumblr.com/".into()),
+            }
+        );
+        assert_eq!(
+            Person::from_str("Barney").unwrap(),
+            Person {
+                name: "Barney".into(),
+                ..Default::default()
+            }
+        );
+        assert!(Person::from_str("<b@rubble.com>").is_err());
+    }
+
+    #[test]
+    fn displays_string() {
+        let person = Person {
+            name: "Barney Rubble".into(),
+            email: Some("b@rubble.com".into()),
+            url: Some("http://barnyrubble.tumblr.com/".into()),
+        };
+
+        assert_eq!(
+            person.to_string(),
+            "Barney Rubble <b@rubble.com> (http://barnyrubble.tumblr.com/)"
+        );
+    }
+
+    #[test]
+    fn supports_author_and_contributors() {
+        let pkg = parse(
+            r#"{
+                "author": "Barney Rubble <b@rubble.com>",
+                "contributors": [{ "name": "Fred", "email": "f@flintstone.com" }, "Wilma", 123]
+            }"#,
+        );
+
+        assert_eq!(
+            pkg.author.unwrap(),
+            Person {
+                name: "Barney Rubble".into(),
+                email: Some("b@rubble.com".into()),
+                url: None,
+            }
+        );
+        assert_eq!(
+            pkg.contributors.unwrap(),
+            vec![
+                Person {
+                    name: "Fred".into(),
+                    email: Some("f@flintstone.com".into()),
+                    url: None,
+                },
+                Person {
+                    name: "Wilma".into(),
+                    ..Default::default()
+                }
+            ]
+        );
+    }
+}
+
+mod repository {
+    use super::*;
+
+    #[test]
+    fn supports_shorthand() {
+        for (value, host) in [
+            ("user/repo", "github"),
+            ("github:user/repo", "github"),
+            ("gitlab:user/repo", "gitlab"),
+            ("bitbucket:user/repo", "bitbucket"),
+        ] {
+            let pkg = parse(&format!(r#"{{ "repository": "{value}" }}"#));
+
+            assert_eq!(
+                pkg.repository.unwrap(),
+                RepositoryField::Shorthand {
+                    host: host.into(),
+                    owner: "user".into(),
+                    repo: "repo".into(),
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn supports_url() {
+        let pkg = parse(r#"{ "repository": "https://github.com/user/repo.git" }"#);
+
+        assert_eq!(
+            pkg.repository.unwrap(),
+            RepositoryField::Url("https://github.com/user/repo.git".into())
+        );
+    }
+
+    #[test]
+    fn supports_object() {
+        let pkg = parse(
+            r#"{ "repository": { "type": "git", "url": "https://github.com/user/repo.git", "directory": "packages/a" } }"#,
+        );
+
+        assert_eq!(
+            pkg.repository.unwrap(),
+            RepositoryField::Object {
+                type_of: Some("git".into()),
+                url: "https://github.com/user/repo.git".into(),
+                directory: Some("packages/a".into()),
+            }
+        );
+    }
+}
+
+#[test]
+fn supports_other_fields() {
+    let pkg = parse(
+        r#"{
+            "private": true,
+            "files": ["lib", "!lib/**/*.test.js"],
+            "keywords": ["a", "b"],
+            "homepage": "https://example.com",
+            "bugs": { "url": "https://example.com/issues" },
+            "funding": ["https://example.com/sponsor", { "type": "patreon", "url": "https://patreon.com/user" }]
+        }"#,
+    );
+
+    assert_eq!(pkg.private, Some(true));
+    assert_eq!(
+        pkg.files.unwrap(),
+        vec!["lib".to_owned(), "!lib/**/*.test.js".to_owned()]
+    );
+    assert_eq!(pkg.keywords.unwrap(), vec!["a".to_owned(), "b".to_owned()]);
+    assert_eq!(pkg.homepage.unwrap(), "https://example.com");
+    assert_eq!(
+        pkg.bugs.unwrap(),
+        BugsField::Object {
+            url: Some("https://example.com/issues".into()),
+            email: None,
+        }
+    );
+    assert_eq!(
+        pkg.funding.unwrap(),
+        FundingField::List(vec![
+            FundingField::Url("https://example.com/sponsor".into()),
+            FundingField::Object {
+                type_of: Some("patreon".into()),
+                url: "https://patreon.com/user".into(),
+            }
+        ])
+    );
+}
+
+#[test]
+fn ignores_invalid_values() {
+    let pkg = parse(
+        r#"{
+            "name": "pkg",
+            "private": "yes",
+            "bin": 123,
+            "files": "lib",
+            "author": "",
+            "license": [],
+            "repository": { "type": "git" }
+        }"#,
+    );
+
+    assert_eq!(pkg.name.unwrap(), "pkg");
+    assert_eq!(pkg.private, None);
+    assert_eq!(pkg.bin, None);
+    assert_eq!(pkg.files, None);
+    assert_eq!(pkg.author, None);
+    assert_eq!(pkg.license, None);
+    assert_eq!(pkg.repository, None);
+}
+
+#[test]
+fn serializes_fields() {
+    let pkg = parse(
+        r#"{
+            "license": "SEE LICENSE IN LICENSE",
+            "repository": "user/repo",
+            "author": "Barney <b@rubble.com>"
+        }"#,
+    );
+
+    assert_eq!(
+        serde_json::to_string(&pkg).unwrap(),
+        r#"{"license":"SEE LICENSE IN LICENSE","repository":"github:user/repo","author":{"name":"Barney","email":"b@rubble.com"}}"#
+    );
+}
@@ -0,0 +1,317 @@
+use nodejs_package_json::*;
+use starbase_sandbox::create_empty_sandbox;
+use std::path::{Path, PathBuf};
+
+fn parse(content: &str) -> PackageJson {
+    serde_json::from_str(content).unwrap()
+}
+
+fn normalize(content: &str) -> PackageJson {
+    let mut package = parse(content);
+    package.normalize(Path::new("/missing"));
+    package
+}
+
+mod repository {
+    use super::*;
+
+    #[test]
+    fn expands_shorthand() {
+        let package = normalize(r#"{ "repository": "gitlab:owner/repo" }"#);
+
+        assert_eq!(
+            package.repository.unwrap(),
+            RepositoryField::Object {
+                type_of: Some("git".into()),
+                url: "git+https://gitlab.com/owner/repo.git".into(),
+                directory: None,
+            }
+        );
+        assert_eq!(
+            package.homepage.unwrap(),
+            "https://gitlab.com/owner/repo#readme"
+        );
+        assert_eq!(
+            package.bugs.unwrap(),
+            BugsField::Object {
+                url: Some("https://gitlab.com/owner/repo/issues".into()),
+                email: None,
+            }
+        );
+    }
+
+    #[test]
+    fn expands_url() {
+        let package = normalize(r#"{ "repository": "git@github.com:owner/repo.git" }"#);
+
+        assert_eq!(
+            package.repository.unwrap(),
+            RepositoryField::Object {
+                type_of: Some("git".into()),
+                url: "git@github.com:owner/repo.git".into(),
+                directory: None,
+            }
+        );
+        assert_eq!(
+            package.homepage.unwrap(),
+            "https://github.com/owner/repo#readme"
+        );
+    }
+
+    #[test]
+    fn doesnt_derive_for_unknown_hosts() {
+        let package = normalize(r#"{ "repository": "https://example.com/owner/repo.git" }"#);
+
+        assert!(package.repository.is_some());
+        assert!(package.homepage.is_none());
+        assert!(package.bugs.is_none());
+    }
+
+    #[test]
+    fn keeps_existing_homepage_and_bugs() {
+        let package = normalize(
+            r#"{
+                "repository": { "type": "git", "url": "https://github.com/owner/repo" },
+                "homepage": "https://example.com",
+                "bugs": "bugs@example.com"
+            }"#,
+        );
+
+        assert_eq!(package.homepage.unwrap(), "https://example.com");
+        assert_eq!(
+            package.bugs.unwrap(),
+            BugsField::Object {
+                url: None,
+                email: Some("bugs@example.com".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn normalizes_bugs_without_repository() {
+        let package = normalize(r#"{ "bugs": "https://example.com/issues" }"#);
+
+        assert_eq!(
+            package.bugs.unwrap(),
+            BugsField::Object {
+                url: Some("https://example.com/issues".into()),
+                email: None,
+            }
+        );
+
+        let package = normalize(
+            r#"{ "repository": "https://example.com/owner/repo.git", "bugs": "bugs@example.com" }"#,
+        );
+
+        assert_eq!(
+            package.bugs.unwrap(),
+            BugsField::Object {
+                url: None,
+                email: Some("bugs@example.com".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_hosted_urls() {
+        for url in [
+            "github:owner/repo",
+            "https://github.com/owner/repo",
+            "git+https://github.com/owner/repo.git#main",
+            "git://github.com/owner/repo.git",
+            "ssh://git@github.com/owner/repo.git",
+            "git@github.com:owner/repo.git",
+        ] {
+            let package = parse(&format!(r#"{{ "repository": "{url}" }}"#));
+            let hosted = package.repository.unwrap().to_hosted();
+
+            assert_eq!(
+                hosted.unwrap(),
+                HostedRepository {
+                    host: "github".into(),
+                    owner: "owner".into(),
+                    repo: "repo".into(),
+                },
+                "{url}"
+            );
+        }
+    }
+}
+
+mod bin {
+    use super::*;
+
+    #[test]
+    fn expands_string_with_name() {
+        let package = normalize(r#"{ "name": "@scope/cli", "bin": "./bin/cli.js" }"#);
+
+        assert_eq!(
+            package.bin.unwrap(),
+            BinField::Map(
+                [("cli".into(), PathBuf::from("bin/cli.js"))]
+                    .into_iter()
+                    .collect()
+            )
+        );
+    }
+
+    #[test]
+    fn secures_map_paths() {
+        let package = normalize(r#"{ "bin": { "a": "./a.js", "../b": "../../b.js" } }"#);
+
+        assert_eq!(
+            package.bin.unwrap(),
+            BinField::Map(
+                [
+                    ("a".into(), PathBuf::from("a.js")),
+                    ("b".into(), PathBuf::from("b.js")),
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+    }
+
+    #[test]
+    fn expands_directories() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("bin/a.js", "");
+        sandbox.create_file("bin/nested/b", "");
+        sandbox.create_file("bin/.hidden", "");
+
+        let mut package = parse(r#"{ "directories": { "bin": "./bin" } }"#);
+
+        package.normalize(sandbox.path());
+
+        assert_eq!(
+            package.bin.unwrap(),
+            BinField::Map(
+                [
+                    ("a.js".into(), PathBuf::from("bin/a.js")),
+                    ("b".into(), PathBuf::from("bin/nested/b")),
+                ]
+                .into_iter()
+                .collect()
+            )
+        );
+    }
+}
+
+#[test]
+fn expands_man_directory() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("man/cli.1", "");
+    sandbox.create_file("man/api.3", "");
+    sandbox.create_file("man/readme.md", "");
+
+    let mut package = parse(r#"{ "directories": { "man": "man" } }"#);
+
+    package.normalize(sandbox.path());
+
+    assert_eq!(
+        package.man.unwrap(),
+        ManField::List(vec![PathBuf::from("man/api.3"), PathBuf::from("man/cli.1")])
+    );
+}
+
+mod bundle_dependencies {
+    use super::*;
+
+    #[test]
+    fn supports_both_spellings() {
+        let a = normalize(r#"{ "bundleDependencies": ["a"] }"#);
+        let b = normalize(r#"{ "bundledDependencies": ["a"] }"#);
+
+        assert_eq!(a.bundle_dependencies, b.bundle_dependencies);
+        assert_eq!(
+            a.bundle_dependencies.unwrap(),
+            BundleDependenciesField::List(vec!["a".into()])
+        );
+        assert!(b.bundled_dependencies.is_none());
+    }
+
+    #[test]
+    fn parses_both_spellings_together() {
+        let package = parse(r#"{ "bundleDependencies": ["a"], "bundledDependencies": ["b"] }"#);
+
+        assert_eq!(
+            package.bundle_dependencies,
+            Some(BundleDependenciesField::List(vec!["a".into()]))
+        );
+        assert_eq!(
+            package.bundled_dependencies,
+            Some(BundleDependenciesField::List(vec!["b".into()]))
+        );
+
+        // The standard spelling takes precedence
+        let package = normalize(r#"{ "bundleDependencies": ["a"], "bundledDependencies": ["b"] }"#);
+
+        assert_eq!(
+            package.bundle_dependencies,
+            Some(BundleDependenciesField::List(vec!["a".into()]))
+        );
+    }
+
+    #[test]
+    fn expands_true_to_dependencies() {
+        let package = normalize(
+            r#"{ "dependencies": { "a": "1.0.0", "b": "2.0.0" }, "bundleDependencies": true }"#,
+        );
+
+        assert_eq!(
+            package.bundle_dependencies.unwrap(),
+            BundleDependenciesField::List(vec!["a".into(), "b".into()])
+        );
+    }
+
+    #[test]
+    fn removes_false() {
+        let package = normalize(r#"{ "bundleDependencies": false }"#);
+
+        assert!(package.bundle_dependencies.is_none());
+    }
+}
+
+mod scripts {
+    use super::*;
+
+    #[test]
+    fn adds_defaults() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("binding.gyp", "");
+        sandbox.create_file("server.js", "");
+
+        let mut package = parse("{}");
+
+        package.normalize(sandbox.path());
+
+        let scripts = package.scripts.unwrap();
+
+        assert_eq!(scripts.get("install").unwrap(), "node-gyp rebuild");
+        assert_eq!(scripts.get("start").unwrap(), "node server.js");
+    }
+
+    #[test]
+    fn keeps_existing() {
+        let sandbox = create_empty_sandbox();
+        sandbox.create_file("binding.gyp", "");
+        sandbox.create_file("server.js", "");
+
+        let mut package =
+            parse(r#"{ "scripts": { "preinstall": "setup", "start": "node index.js" } }"#);
+
+        package.normalize(sandbox.path());
+
+        let scripts = package.scripts.unwrap();
+
+        assert!(!scripts.contains_key("install"));
+        assert_eq!(scripts.get("start").unwrap(), "node index.js");
+    }
+
+    #[test]
+    fn doesnt_add_empty() {
+        let package = normalize("{}");
+
+        assert!(package.scripts.is_none());
+    }
+}
@@ -0,0 +1,243 @@
+use nodejs_package_json::{
+    OverrideSelector, OverrideSource, OverrideValue, PackageJson, Range, VersionProtocol,
+};
+use std::str::FromStr;
+
+fn parse(content: &str) -> PackageJson {
+    serde_json::from_str(content).unwrap()
+}
+
+fn find(pkg: &PackageJson, path: &[&str]) -> Option<(String, Option<String>)> {
+    let rules = pkg.get_override_rules();
+
+    pkg.find_override(&rules, path).map(|result| {
+        (
+            result.rule.key.clone(),
+            result.version.map(|version| version.to_string()),
+        )
+    })
+}
+
+fn package(name: &str, range: Option<&str>) -> OverrideSelector {
+    OverrideSelector::Package {
+        name: name.into(),
+        range: range.map(|range| Range::parse(range).unwrap()),
+    }
+}
+
+mod npm {
+    use super::*;
+
+    #[test]
+    fn parses_nested_rules() {
+        let pkg = parse(
+            r#"{
+                "overrides": {
+                    "foo": "1.0.0",
+                    "bar@2": { ".": "2.1.0", "baz": "$baz" }
+                }
+            }"#,
+        );
+        let rules = pkg.get_override_rules();
+
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0].source, OverrideSource::Npm);
+        assert_eq!(
+            rules[0].selectors,
+            vec![OverrideSelector::AnyDepth, package("foo", None)]
+        );
+        assert_eq!(
+            rules[1].selectors,
+            vec![OverrideSelector::AnyDepth, package("bar", Some("2"))]
+        );
+        assert_eq!(rules[2].key, "bar@2 > baz");
+        assert_eq!(
+            rules[2].selectors,
+            vec![
+                OverrideSelector::AnyDepth,
+                package("bar", Some("2")),
+                OverrideSelector::AnyDepth,
+                package("baz", None)
+            ]
+        );
+        assert_eq!(rules[2].value, OverrideValue::Reference("baz".into()));
+    }
+
+    #[test]
+    fn finds_matching_override() {
+        let pkg = parse(
+            r#"{
+                "dependencies": { "baz": "^3.0.0" },
+                "overrides": {
+                    "baz": "1.0.0",
+                    "bar@2": { ".": "2.1.0", "baz": "$baz" }
+                }
+            }"#,
+        );
+
+        assert_eq!(
+            find(&pkg, &["a", "baz"]),
+            Some(("baz".into(), Some("1.0.0".into())))
+        );
+        assert_eq!(
+            find(&pkg, &["bar@2.0.0"]),
+            Some(("bar@2".into(), Some("2.1.0".into())))
+        );
+        // More specific wins
+        assert_eq!(
+            find(&pkg, &["a", "bar@2.0.0", "c", "baz"]),
+            Some(("bar@2 > baz".into(), Some("^3.0.0".into())))
+        );
+        // Range does not match
+        assert_eq!(
+            find(&pkg, &["bar@3.0.0", "baz"]),
+            Some(("baz".into(), Some("1.0.0".into())))
+        );
+        assert_eq!(find(&pkg, &["a", "b"]), None);
+    }
+}
+
+mod yarn {
+    use super::*;
+
+    #[test]
+    fn parses_path_selectors() {
+        let pkg = parse(
+            r#"{
+                "resolutions": {
+                    "foo": "1.0.0",
+                    "**/@scope/bar": "2.0.0",
+                    "a/b": "3.0.0",
+                    "@scope/a/**/c": "npm:4.0.0"
+                }
+            }"#,
+        );
+        let rules = pkg.get_override_rules();
+
+        assert_eq!(rules.len(), 4);
+        assert_eq!(rules[0].source, OverrideSource::Yarn);
+        assert_eq!(
+            rules[0].selectors,
+            vec![OverrideSelector::AnyDepth, package("foo", None)]
+        );
+        assert_eq!(
+            rules[1].selectors,
+            vec![OverrideSelector::AnyDepth, package("@scope/bar", None)]
+        );
+        assert_eq!(
+            rules[2].selectors,
+            vec![package("a", None), package("b", None)]
+        );
+        assert_eq!(
+            rules[3].selectors,
+            vec![
+                package("@scope/a", None),
+                OverrideSelector::AnyDepth,
+                package("c", None)
+            ]
+        );
+        assert_eq!(
+            rules[3].value,
+            OverrideValue::Version(VersionProtocol::from_str("4.0.0").unwrap())
+        );
+    }
+
+    #[test]
+    fn finds_matching_override() {
+        let pkg = parse(
+            r#"{
+                "resolutions": {
+                    "b": "1.0.0",
+                    "a/b": "2.0.0"
+                }
+            }"#,
+        );
+
+        assert_eq!(
+            find(&pkg, &["a", "b"]),
+            Some(("a/b".into(), Some("2.0.0".into())))
+        );
+        // Path selectors are anchored to the root
+        assert_eq!(
+            find(&pkg, &["x", "a", "b"]),
+            Some(("b".into(), Some("1.0.0".into())))
+        );
+    }
+}
+
+mod pnpm {
+    use super::*;
+
+    #[test]
+    fn parses_parent_selectors() {
+        let pkg = parse(
+            r#"{
+                "pnpm": {
+                    "overrides": {
+                        "foo": "^1.0.0",
+                        "foo@1>bar": "2.0.0",
+                        "baz": "-"
+                    },
+                    "neverBuiltDependencies": ["fsevents"]
+                }
+            }"#,
+        );
+        let rules = pkg.get_override_rules();
+
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[1].source, OverrideSource::Pnpm);
+        assert_eq!(
+            rules[1].selectors,
+            vec![
+                OverrideSelector::AnyDepth,
+                package("foo", Some("1")),
+                package("bar", None)
+            ]
+        );
+        assert_eq!(rules[2].value, OverrideValue::Remove);
+        assert!(pkg
+            .pnpm
+            .unwrap()
+            .other_fields
+            .contains_key("neverBuiltDependencies"));
+    }
+
+    #[test]
+    fn finds_matching_override() {
+        let pkg = parse(
+            r#"{
+                "pnpm": {
+                    "overrides": {
+                        "bar": "1.0.0",
+                        "foo@1>bar": "2.0.0",
+                        "baz": "-"
+                    }
+                }
+            }"#,
+        );
+
+        assert_eq!(
+            find(&pkg, &["x", "foo@1.2.0", "bar"]),
+            Some(("foo@1>bar".into(), Some("2.0.0".into())))
+        );
+        // Must be a direct dependency of the parent
+        assert_eq!(
+            find(&pkg, &["foo@1.2.0", "x", "bar"]),
+            Some(("bar".into(), Some("1.0.0".into())))
+        );
+        assert_eq!(find(&pkg, &["baz"]), Some(("baz".into(), None)));
+    }
+}
+
+#[test]
+fn ignores_invalid_rules() {
+    let pkg = parse(
+        r#"{
+            "overrides": { "foo@not a range": "1.0.0" },
+            "resolutions": { "@scope": "1.0.0" },
+            "pnpm": { "overrides": { "a>": "1.0.0" } }
+        }"#,
+    );
+
+    assert!(pkg.get_override_rules().is_empty());
+}
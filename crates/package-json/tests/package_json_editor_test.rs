@@ -0,0 +1,367 @@
+use nodejs_package_json::{
+    DependencyType, JsonValue, PackageJsonEditor, PackageJsonEditorError, Version,
+};
+use starbase_sandbox::create_empty_sandbox;
+use std::fs;
+
+const MANIFEST: &str = r#"{
+  "name": "pkg",
+  "version": "1.0.0",
+  "scripts": {
+    "test": "jest",
+    "build": "tsc"
+  },
+  "dependencies": {
+    "a": "^1.0.0",
+    "c": "^3.0.0"
+  },
+  "devDependencies": {
+    "z": "^1.0.0",
+    "b": "^2.0.0"
+  },
+  "zzz": true,
+  "aaa": false
+}
+"#;
+
+#[test]
+fn preserves_formatting_without_changes() {
+    let editor = PackageJsonEditor::new(MANIFEST).unwrap();
+
+    assert_eq!(editor.to_string(), MANIFEST);
+}
+
+#[test]
+fn preserves_tabs_and_crlf() {
+    let content = "{\r\n\t\"name\": \"pkg\",\r\n\t\"private\": true\r\n}";
+    let mut editor = PackageJsonEditor::new(content).unwrap();
+
+    editor.set_version(Version::new(1, 2, 3));
+
+    assert_eq!(
+        editor.to_string(),
+        "{\r\n\t\"name\": \"pkg\",\r\n\t\"private\": true,\r\n\t\"version\": \"1.2.3\"\r\n}"
+    );
+}
+
+#[test]
+fn detects_indent_width() {
+    let content = "{\n    \"name\": \"pkg\",\n    \"files\": [\n        \"lib\"\n    ]\n}\n";
+    let editor = PackageJsonEditor::new(content).unwrap();
+
+    assert_eq!(editor.to_string(), content);
+}
+
+#[test]
+fn preserves_inline_values_when_editing() {
+    let content = r#"{
+  "name": "pkg",
+  "version": "1.0.0",
+  "files": ["dist", "lib"],
+  "engines": { "node": ">=18" },
+  "keywords": [],
+  "publishConfig": {"access": "public"},
+  "scripts": {
+    "build": "tsc",
+    "test": "jest"
+  }
+}
+"#;
+    let mut editor = PackageJsonEditor::new(content).unwrap();
+
+    editor.set_version("1.1.0");
+
+    assert_eq!(
+        editor.to_string(),
+        content.replace(r#""version": "1.0.0""#, r#""version": "1.1.0""#)
+    );
+
+    editor
+        .set_field("files", vec!["dist", "lib", "types"])
+        .unwrap();
+    editor
+        .set_field("publishConfig", JsonValue::Object(Default::default()))
+        .unwrap();
+    editor.set_script("test", "vitest").unwrap();
+
+    assert_eq!(
+        editor.to_string(),
+        content
+            .replace(r#""version": "1.0.0""#, r#""version": "1.1.0""#)
+            .replace(r#"["dist", "lib"]"#, r#"["dist", "lib", "types"]"#)
+            .replace(r#"{"access": "public"}"#, "{}")
+            .replace(r#""test": "jest""#, r#""test": "vitest""#)
+    );
+}
+
+#[test]
+fn sets_version_in_place() {
+    let mut editor = PackageJsonEditor::new(MANIFEST).unwrap();
+
+    editor.set_version("2.0.0");
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.replace(r#""version": "1.0.0""#, r#""version": "2.0.0""#)
+    );
+}
+
+#[test]
+fn sets_scripts() {
+    let mut editor = PackageJsonEditor::new(MANIFEST).unwrap();
+
+    editor.set_script("build", "tsc --build").unwrap();
+    editor.set_script("lint", "eslint .").unwrap();
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.replace(
+            r#""build": "tsc"
+  },"#,
+            r#""build": "tsc --build",
+    "lint": "eslint ."
+  },"#
+        )
+    );
+}
+
+#[test]
+fn adds_dependency_in_sorted_order() {
+    let mut editor = PackageJsonEditor::new(MANIFEST).unwrap();
+
+    editor
+        .add_dependency(DependencyType::Production, "b", "^2.0.0")
+        .unwrap();
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.replace(
+            r#""a": "^1.0.0","#,
+            r#""a": "^1.0.0",
+    "b": "^2.0.0","#
+        )
+    );
+}
+
+#[test]
+fn adds_dependency_to_end_if_unsorted() {
+    let mut editor = PackageJsonEditor::new(MANIFEST).unwrap();
+
+    editor
+        .add_dependency(DependencyType::Development, "a", "^1.0.0")
+        .unwrap();
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.replace(
+            r#""b": "^2.0.0""#,
+            r#""b": "^2.0.0",
+    "a": "^1.0.0""#
+        )
+    );
+}
+
+#[test]
+fn adds_dependency_section() {
+    let mut editor = PackageJsonEditor::new(MANIFEST).unwrap();
+
+    editor
+        .add_dependency(DependencyType::Peer, "react", "^18.0.0")
+        .unwrap();
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.replace(
+            "\"aaa\": false\n",
+            "\"aaa\": false,\n  \"peerDependencies\": {\n    \"react\": \"^18.0.0\"\n  }\n"
+        )
+    );
+}
+
+#[test]
+fn removes_dependency() {
+    let mut editor = PackageJsonEditor::new(MANIFEST).unwrap();
+
+    assert!(editor.remove_dependency(DependencyType::Production, "a"));
+    assert!(!editor.remove_dependency(DependencyType::Production, "unknown"));
+    assert!(!editor.remove_dependency(DependencyType::Optional, "a"));
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.replace("\n    \"a\": \"^1.0.0\",", "")
+    );
+}
+
+#[test]
+fn removes_empty_dependency_section() {
+    let mut editor = PackageJsonEditor::new(MANIFEST).unwrap();
+
+    editor.remove_dependency(DependencyType::Development, "z");
+    editor.remove_dependency(DependencyType::Development, "b");
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.replace(
+            r#"  "devDependencies": {
+    "z": "^1.0.0",
+    "b": "^2.0.0"
+  },
+"#,
+            ""
+        )
+    );
+}
+
+#[test]
+fn renames_dependency() {
+    let mut editor = PackageJsonEditor::new(MANIFEST).unwrap();
+
+    // Unsorted keeps position
+    assert!(editor.rename_dependency(DependencyType::Development, "z", "y"));
+    // Sorted re-sorts
+    assert!(editor.rename_dependency(DependencyType::Production, "a", "d"));
+    assert!(!editor.rename_dependency(DependencyType::Production, "unknown", "e"));
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST
+            .replace(r#""z": "^1.0.0""#, r#""y": "^1.0.0""#)
+            .replace(
+                r#""a": "^1.0.0",
+    "c": "^3.0.0""#,
+                r#""c": "^3.0.0",
+    "d": "^1.0.0""#
+            )
+    );
+}
+
+#[test]
+fn renames_dependency_to_existing_name() {
+    let mut editor = PackageJsonEditor::new(
+        r#"{
+  "dependencies": {
+    "z": "^1.0.0",
+    "b": "^2.0.0",
+    "x": "^3.0.0",
+    "a": "^4.0.0"
+  }
+}"#,
+    )
+    .unwrap();
+
+    assert!(editor.rename_dependency(DependencyType::Production, "x", "z"));
+
+    assert_eq!(
+        editor.to_string(),
+        r#"{
+  "dependencies": {
+    "b": "^2.0.0",
+    "z": "^3.0.0",
+    "a": "^4.0.0"
+  }
+}"#
+    );
+}
+
+#[test]
+fn preserves_nested_key_order() {
+    let mut editor = PackageJsonEditor::new(MANIFEST).unwrap();
+
+    editor
+        .set_field(
+            "exports",
+            JsonValue::Object(
+                [
+                    ("./z".to_owned(), JsonValue::String("./z.js".into())),
+                    ("./a".to_owned(), JsonValue::String("./a.js".into())),
+                ]
+                .into_iter()
+                .collect(),
+            ),
+        )
+        .unwrap();
+
+    assert!(editor
+        .to_string()
+        .contains("\"exports\": {\n    \"./z\": \"./z.js\",\n    \"./a\": \"./a.js\"\n  }"));
+    assert_eq!(
+        editor
+            .to_manifest()
+            .unwrap()
+            .scripts
+            .unwrap()
+            .keys()
+            .collect::<Vec<_>>(),
+        vec!["test", "build"]
+    );
+}
+
+#[test]
+fn sets_and_removes_fields() {
+    let mut editor = PackageJsonEditor::new(MANIFEST).unwrap();
+
+    editor.set_field("zzz", false).unwrap();
+    editor.set_field("files", vec!["lib"]).unwrap();
+
+    assert_eq!(editor.remove_field("aaa"), Some(JsonValue::Bool(false)));
+    assert_eq!(editor.remove_field("unknown"), None);
+
+    assert_eq!(
+        editor.to_string(),
+        MANIFEST.replace(
+            "\"zzz\": true,\n  \"aaa\": false\n",
+            "\"zzz\": false,\n  \"files\": [\n    \"lib\"\n  ]\n"
+        )
+    );
+}
+
+#[test]
+fn converts_to_manifest() {
+    let mut editor = PackageJsonEditor::new(MANIFEST).unwrap();
+
+    editor.set_version("1.1.0");
+
+    let manifest = editor.to_manifest().unwrap();
+
+    assert_eq!(manifest.name.unwrap(), "pkg");
+    assert_eq!(manifest.version.unwrap(), Version::new(1, 1, 0));
+}
+
+#[test]
+fn loads_and_saves_file() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("package.json", MANIFEST);
+
+    let path = sandbox.path().join("package.json");
+    let mut editor = PackageJsonEditor::load(&path).unwrap();
+    editor.set_version("3.0.0");
+    editor.save().unwrap();
+
+    assert_eq!(
+        fs::read_to_string(&path).unwrap(),
+        MANIFEST.replace(r#""version": "1.0.0""#, r#""version": "3.0.0""#)
+    );
+}
+
+#[test]
+fn errors_for_invalid_content() {
+    assert!(matches!(
+        PackageJsonEditor::new("[]").unwrap_err(),
+        PackageJsonEditorError::InvalidRoot
+    ));
+    assert!(matches!(
+        PackageJsonEditor::new("{").unwrap_err(),
+        PackageJsonEditorError::Json(_)
+    ));
+    assert!(matches!(
+        PackageJsonEditor::new(r#"{ "scripts": [] }"#)
+            .unwrap()
+            .set_script("test", "jest")
+            .unwrap_err(),
+        PackageJsonEditorError::NotAnObject(_)
+    ));
+    assert!(matches!(
+        PackageJsonEditor::new("{}").unwrap().save().unwrap_err(),
+        PackageJsonEditorError::NoPath
+    ));
+}
@@ -1,9 +1,9 @@
 use indexmap::IndexMap;
 use nodejs_package_json::{
-    DependencyMetaField, ImportExportField, ImportExportMap, PackageJson, VersionProtocol,
+    DependencyMetaField, ImportExportField, ImportExportMap, PackageJson, Range, VersionProtocol,
     WorkspaceProtocol,
 };
-use semver::{Version, VersionReq};
+use semver::Version;
 use std::collections::BTreeMap;
 
 #[test]
@@ -34,7 +34,7 @@ fn serializes_packages_json() {
         dependencies: Some(BTreeMap::from_iter([
             (
                 "@moonrepo/cli".into(),
-                VersionProtocol::Requirement(VersionReq::parse("^1.20").unwrap()),
+                VersionProtocol::Requirement(Range::parse("^1.20").unwrap()),
             ),
             (
                 "vite".into(),
@@ -0,0 +1,141 @@
+use nodejs_package_json::{
+    DevEngine, DevEngineField, DevEngineOnFail, PackageJson, PackageManagerField,
+    PackageManagerFieldError, PackageManagerHash, Range,
+};
+use nodejs_package_managers::PackageManager;
+use semver::Version;
+use std::str::FromStr;
+
+#[test]
+fn parses_manager_and_version() {
+    let exp = PackageManagerField {
+        manager: PackageManager::Pnpm,
+        version: Version::new(9, 1, 0),
+        hash: None,
+    };
+
+    assert_eq!(PackageManagerField::from_str("pnpm@9.1.0").unwrap(), exp);
+    assert_eq!(exp.to_string(), "pnpm@9.1.0");
+}
+
+#[test]
+fn parses_hash() {
+    let exp = PackageManagerField {
+        manager: PackageManager::Npm,
+        version: Version::new(10, 8, 1),
+        hash: Some(PackageManagerHash {
+            algorithm: "sha512".into(),
+            digest: "abc123def456".into(),
+        }),
+    };
+
+    assert_eq!(
+        PackageManagerField::from_str("npm@10.8.1+sha512.abc123def456").unwrap(),
+        exp
+    );
+    assert_eq!(exp.to_string(), "npm@10.8.1+sha512.abc123def456");
+}
+
+#[test]
+fn maps_yarn_by_version() {
+    assert_eq!(
+        PackageManagerField::from_str("yarn@1.22.19")
+            .unwrap()
+            .manager,
+        PackageManager::YarnLegacy
+    );
+    assert_eq!(
+        PackageManagerField::from_str("yarn@4.3.1").unwrap().manager,
+        PackageManager::Yarn
+    );
+    assert_eq!(
+        PackageManagerField::from_str("yarn@1.22.19")
+            .unwrap()
+            .to_string(),
+        "yarn@1.22.19"
+    );
+}
+
+#[test]
+fn errors_for_invalid_values() {
+    assert!(matches!(
+        PackageManagerField::from_str("pnpm").unwrap_err(),
+        PackageManagerFieldError::MissingVersion(_)
+    ));
+    assert!(matches!(
+        PackageManagerField::from_str("deno@1.0.0").unwrap_err(),
+        PackageManagerFieldError::UnknownManager(_)
+    ));
+    assert!(matches!(
+        PackageManagerField::from_str("pnpm@^9").unwrap_err(),
+        PackageManagerFieldError::Semver(_)
+    ));
+    assert!(matches!(
+        PackageManagerField::from_str("pnpm@9.0.0+abc").unwrap_err(),
+        PackageManagerFieldError::InvalidHash(_)
+    ));
+}
+
+#[test]
+fn parses_field_in_manifest() {
+    let pkg: PackageJson =
+        serde_json::from_str(r#"{ "name": "pkg", "packageManager": "pnpm@9.1.0" }"#).unwrap();
+
+    assert_eq!(
+        pkg.parse_package_manager().unwrap().unwrap().manager,
+        PackageManager::Pnpm
+    );
+    assert_eq!(
+        PackageJson::default().parse_package_manager().unwrap(),
+        None
+    );
+}
+
+#[test]
+fn preserves_invalid_field_in_manifest() {
+    let pkg: PackageJson =
+        serde_json::from_str(r#"{ "name": "pkg", "packageManager": "yarn@berry" }"#).unwrap();
+
+    assert_eq!(pkg.package_manager.as_deref(), Some("yarn@berry"));
+    assert!(matches!(
+        pkg.parse_package_manager().unwrap_err(),
+        PackageManagerFieldError::Semver(_)
+    ));
+}
+
+#[test]
+fn parses_dev_engines() {
+    let pkg: PackageJson = serde_json::from_str(
+        r#"{
+            "devEngines": {
+                "runtime": { "name": "node", "version": ">=20", "onFail": "download" },
+                "packageManager": [{ "name": "pnpm", "version": "^9" }, { "name": "npm" }]
+            }
+        }"#,
+    )
+    .unwrap();
+    let dev_engines = pkg.dev_engines.unwrap();
+
+    assert_eq!(
+        dev_engines.runtime.unwrap(),
+        DevEngineField::One(DevEngine {
+            name: "node".into(),
+            version: Some(Range::parse(">=20").unwrap()),
+            on_fail: Some(DevEngineOnFail::Download),
+        })
+    );
+    assert_eq!(
+        dev_engines.package_manager.unwrap().to_list(),
+        vec![
+            &DevEngine {
+                name: "pnpm".into(),
+                version: Some(Range::parse("^9").unwrap()),
+                on_fail: None,
+            },
+            &DevEngine {
+                name: "npm".into(),
+                ..Default::default()
+            }
+        ]
+    );
+}
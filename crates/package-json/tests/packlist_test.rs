@@ -0,0 +1,213 @@
+use nodejs_package_json::PackageJson;
+use starbase_sandbox::create_empty_sandbox;
+use std::fs;
+use std::path::Path;
+
+fn pack(dir: &Path) -> Vec<String> {
+    let package: PackageJson =
+        serde_json::from_str(&fs::read_to_string(dir.join("package.json")).unwrap()).unwrap();
+
+    package
+        .get_pack_list(dir)
+        .unwrap()
+        .files
+        .into_iter()
+        .map(|file| file.to_string_lossy().replace('\\', "/"))
+        .collect()
+}
+
+#[test]
+fn includes_everything_by_default() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("package.json", "{}");
+    sandbox.create_file("index.js", "");
+    sandbox.create_file("lib/util.js", "");
+    sandbox.create_file("package-lock.json", "");
+    sandbox.create_file("yarn.lock", "");
+    sandbox.create_file("node_modules/dep/index.js", "");
+    sandbox.create_file(".git/HEAD", "");
+    sandbox.create_file(".npmrc", "");
+    sandbox.create_file("lib/.DS_Store", "");
+
+    assert_eq!(
+        pack(sandbox.path()),
+        ["index.js", "lib/util.js", "package.json"]
+    );
+}
+
+#[test]
+fn includes_shrinkwrap() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("package.json", "{}");
+    sandbox.create_file("index.js", "");
+    sandbox.create_file("npm-shrinkwrap.json", "{}");
+    sandbox.create_file("pnpm-lock.yaml", "");
+    sandbox.create_file("bun.lockb", "");
+
+    assert_eq!(
+        pack(sandbox.path()),
+        ["index.js", "npm-shrinkwrap.json", "package.json"]
+    );
+}
+
+#[test]
+fn uses_gitignore() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("package.json", "{}");
+    sandbox.create_file(".gitignore", "dist\n*.log\n");
+    sandbox.create_file("index.js", "");
+    sandbox.create_file("debug.log", "");
+    sandbox.create_file("dist/index.js", "");
+
+    assert_eq!(pack(sandbox.path()), ["index.js", "package.json"]);
+}
+
+#[test]
+fn prefers_npmignore_over_gitignore() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("package.json", "{}");
+    sandbox.create_file(".gitignore", "dist\n");
+    sandbox.create_file(".npmignore", "src\n");
+    sandbox.create_file("dist/index.js", "");
+    sandbox.create_file("src/index.ts", "");
+
+    assert_eq!(pack(sandbox.path()), ["dist/index.js", "package.json"]);
+}
+
+#[test]
+fn uses_nested_ignore_files() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("package.json", r#"{ "files": ["lib"] }"#);
+    sandbox.create_file("lib/.npmignore", "*.test.js\n");
+    sandbox.create_file("lib/index.js", "");
+    sandbox.create_file("lib/index.test.js", "");
+
+    assert_eq!(pack(sandbox.path()), ["lib/index.js", "package.json"]);
+}
+
+#[test]
+fn uses_files_with_negations() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(
+        "package.json",
+        r#"{ "files": ["./dist/", "!dist/**/*.map", "types/*.d.ts"] }"#,
+    );
+    sandbox.create_file(".npmignore", "dist\n");
+    sandbox.create_file("dist/index.js", "");
+    sandbox.create_file("dist/index.js.map", "");
+    sandbox.create_file("dist/nested/util.js", "");
+    sandbox.create_file("types/index.d.ts", "");
+    sandbox.create_file("types/index.ts", "");
+    sandbox.create_file("src/index.ts", "");
+
+    assert_eq!(
+        pack(sandbox.path()),
+        [
+            "dist/index.js",
+            "dist/nested/util.js",
+            "package.json",
+            "types/index.d.ts"
+        ]
+    );
+}
+
+#[test]
+fn skips_directories_outside_files() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(
+        "package.json",
+        r#"{ "files": ["dist/esm/*.js", "**/*.d.ts"] }"#,
+    );
+    sandbox.create_file("dist/esm/index.js", "");
+    sandbox.create_file("dist/cjs/index.js", "");
+    sandbox.create_file("src/types/index.d.ts", "");
+    sandbox.create_file("src/index.ts", "");
+
+    assert_eq!(
+        pack(sandbox.path()),
+        ["dist/esm/index.js", "package.json", "src/types/index.d.ts"]
+    );
+
+    // An invalid ignore file is never read when its directory can't match
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("package.json", r#"{ "files": ["dist/esm"] }"#);
+    sandbox.create_file("dist/esm/index.js", "");
+    sandbox.create_file("dist/cjs/.npmignore", "[\n");
+    sandbox.create_file("src/.npmignore", "[\n");
+
+    assert_eq!(pack(sandbox.path()), ["dist/esm/index.js", "package.json"]);
+
+    // Without `files`, every directory is read
+    assert!(PackageJson::default()
+        .get_pack_list(sandbox.path())
+        .is_err());
+}
+
+#[test]
+fn always_includes_required_files() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file(
+        "package.json",
+        r#"{ "main": "./main.js", "bin": { "cli": "./bin/cli.js" }, "files": [] }"#,
+    );
+    sandbox.create_file(".npmignore", "*\n");
+    sandbox.create_file("README.md", "");
+    sandbox.create_file("LICENSE", "");
+    sandbox.create_file("Licence.txt", "");
+    sandbox.create_file("CHANGELOG.md", "");
+    sandbox.create_file("main.js", "");
+    sandbox.create_file("bin/cli.js", "");
+    sandbox.create_file("other.js", "");
+
+    assert_eq!(
+        pack(sandbox.path()),
+        [
+            "LICENSE",
+            "Licence.txt",
+            "README.md",
+            "bin/cli.js",
+            "main.js",
+            "package.json"
+        ]
+    );
+}
+
+#[test]
+fn calculates_size() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("package.json", "{}");
+    sandbox.create_file("a.js", "12345");
+    sandbox.create_file("b/c.js", "123");
+
+    let package = PackageJson::default();
+    let list = package.get_pack_list(sandbox.path()).unwrap();
+
+    assert_eq!(list.files.len(), 3);
+    assert_eq!(list.size, 10);
+}
+
+#[test]
+fn always_includes_prefixed_root_files() {
+    let sandbox = create_empty_sandbox();
+    sandbox.create_file("package.json", r#"{ "files": ["dist"] }"#);
+    sandbox.create_file("LICENSE-MIT", "");
+    sandbox.create_file("LICENSE_APACHE", "");
+    sandbox.create_file("README-zh.md", "");
+    sandbox.create_file("copying.txt", "");
+    sandbox.create_file("dist/index.js", "");
+    sandbox.create_file("dist/README-internal.md", "");
+    sandbox.create_file("src/index.ts", "");
+
+    assert_eq!(
+        pack(sandbox.path()),
+        [
+            "LICENSE-MIT",
+            "LICENSE_APACHE",
+            "README-zh.md",
+            "copying.txt",
+            "dist/README-internal.md",
+            "dist/index.js",
+            "package.json"
+        ]
+    );
+}
@@ -0,0 +1,475 @@
+use nodejs_package_json::{Comparator, Range};
+use semver::Version;
+
+fn range(value: &str) -> Range {
+    Range::parse(value).unwrap()
+}
+
+fn version(value: &str) -> Version {
+    Version::parse(value).unwrap()
+}
+
+mod parse {
+    use super::*;
+
+    #[test]
+    fn desugars_comparators() {
+        for (input, expected) in [
+            ("", "*"),
+            ("*", "*"),
+            ("x", "*"),
+            (">=*", "*"),
+            ("1.2.3", "1.2.3"),
+            ("=1.2.3", "1.2.3"),
+            ("v1.2.3", "1.2.3"),
+            (">= 1.2.3", ">=1.2.3"),
+            ("1.x", ">=1.0.0 <2.0.0-0"),
+            ("1.2.*", ">=1.2.0 <1.3.0-0"),
+            ("1.2", ">=1.2.0 <1.3.0-0"),
+            (">1", ">=2.0.0"),
+            (">1.2", ">=1.3.0"),
+            ("<1.2", "<1.2.0-0"),
+            ("<=1.2", "<1.3.0-0"),
+            ("<*", "<0.0.0-0"),
+            ("^1.2.3", ">=1.2.3 <2.0.0-0"),
+            ("^0.2.3", ">=0.2.3 <0.3.0-0"),
+            ("^0.0.3", ">=0.0.3 <0.0.4-0"),
+            ("^1.2.3-beta.2", ">=1.2.3-beta.2 <2.0.0-0"),
+            ("^1.x", ">=1.0.0 <2.0.0-0"),
+            ("~1.2.3", ">=1.2.3 <1.3.0-0"),
+            ("~1.2", ">=1.2.0 <1.3.0-0"),
+            ("~1", ">=1.0.0 <2.0.0-0"),
+            ("~> 1.2.3", ">=1.2.3 <1.3.0-0"),
+            ("1.2.3 - 2.3.4", ">=1.2.3 <=2.3.4"),
+            ("1.2 - 2.3.4", ">=1.2.0 <=2.3.4"),
+            ("1.2.3 - 2.3", ">=1.2.3 <2.4.0-0"),
+            ("1.2.3 - 2", ">=1.2.3 <3.0.0-0"),
+            ("1.2.3 - 2.3.4-beta", ">=1.2.3 <=2.3.4-beta"),
+        ] {
+            let set = range(input).comparator_sets()[0]
+                .iter()
+                .map(|comp| comp.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            assert_eq!(set, expected, "{input}");
+        }
+    }
+
+    #[test]
+    fn splits_on_or() {
+        let range = range("^1.2.3 || >=3 <4 || 5.x");
+
+        assert_eq!(range.comparator_sets().len(), 3);
+        assert_eq!(range.to_string(), "^1.2.3 || >=3 <4 || 5.x");
+    }
+
+    #[test]
+    fn collapses_to_any() {
+        assert!(range("1.x || *").is_any());
+        assert!(range("").is_any());
+        assert!(!range("1.x").is_any());
+    }
+
+    #[test]
+    fn removes_null_sets() {
+        assert_eq!(
+            range("<0.0.0-0 || 1.2.3").comparator_sets(),
+            &[vec![Comparator::Eq(version("1.2.3"))]]
+        );
+    }
+
+    #[test]
+    fn errors_for_invalid() {
+        for input in [
+            "latest", "1.2.3.4", "01.2.3", ">=", "1.x-beta", "1.2-beta", "1.2.3 - ",
+        ] {
+            assert!(Range::parse(input).is_err(), "{input}");
+        }
+    }
+
+    #[test]
+    fn errors_for_parts_above_max_safe_integer() {
+        for input in [
+            "^18446744073709551615",
+            "~18446744073709551615",
+            "<=1.18446744073709551615",
+            "1 - 18446744073709551615",
+            "9007199254740992.0.0",
+        ] {
+            assert!(Range::parse(input).is_err(), "{input}");
+        }
+
+        assert_eq!(
+            range("^9007199254740991").comparator_sets()[0][1].to_string(),
+            "<9007199254740992.0.0-0"
+        );
+    }
+}
+
+mod satisfies {
+    use super::*;
+
+    #[test]
+    fn includes() {
+        for (input, ver) in [
+            ("1.0.0 - 2.0.0", "1.2.3"),
+            ("^1.2.3+build", "1.2.3"),
+            ("^1.2.3+build", "1.3.0"),
+            ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "1.2.3"),
+            ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "1.2.3-pre.2"),
+            ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "2.4.3-alpha"),
+            ("1.2.3+asdf - 2.4.3+asdf", "1.2.3"),
+            ("1.0.0", "1.0.0"),
+            (">=*", "0.2.4"),
+            ("", "1.0.0"),
+            ("*", "1.2.3"),
+            (">=1.0.0", "1.0.0"),
+            (">=1.0.0", "1.1.0"),
+            (">1.0.0", "1.0.1"),
+            ("<=2.0.0", "2.0.0"),
+            ("<=2.0.0", "0.2.9"),
+            ("<2.0.0", "1.9999.9999"),
+            (">= 1.0.0", "1.0.0"),
+            ("> 1.0.0", "1.1.0"),
+            ("<=   2.0.0", "1.9999.9999"),
+            ("0.1.20 || 1.2.4", "1.2.4"),
+            (">=0.2.3 || <0.0.1", "0.0.0"),
+            (">=0.2.3 || <0.0.1", "0.2.3"),
+            ("2.x.x", "2.1.3"),
+            ("1.2.x", "1.2.3"),
+            ("1.2.x || 2.x", "2.1.3"),
+            ("x", "1.2.3"),
+            ("2.*.*", "2.1.3"),
+            ("2", "2.1.2"),
+            ("2.3", "2.3.1"),
+            ("~0.0.1", "0.0.1"),
+            ("~0.0.1", "0.0.2"),
+            ("~x", "0.0.9"),
+            ("~2", "2.0.9"),
+            ("~2.4", "2.4.0"),
+            ("~2.4", "2.4.5"),
+            ("~>3.2.1", "3.2.2"),
+            ("~1", "1.2.3"),
+            ("~>1", "1.2.3"),
+            ("~> 1", "1.2.3"),
+            ("~1.0", "1.0.2"),
+            ("~ 1.0", "1.0.2"),
+            ("~ 1.0.3", "1.0.12"),
+            (">=1", "1.0.0"),
+            (">= 1", "1.0.0"),
+            ("<1.2", "1.1.1"),
+            ("< 1.2", "1.1.1"),
+            ("~v0.5.4-pre", "0.5.5"),
+            ("~v0.5.4-pre", "0.5.4"),
+            ("=0.7.x", "0.7.2"),
+            ("<=0.7.x", "0.7.2"),
+            (">=0.7.x", "0.7.2"),
+            ("<=0.7.x", "0.6.2"),
+            ("~1.2.1 >=1.2.3", "1.2.3"),
+            ("~1.2.1 =1.2.3", "1.2.3"),
+            ("~1.2.1 1.2.3", "1.2.3"),
+            (">=1.2.1 1.2.3", "1.2.3"),
+            ("1.2.3 >=1.2.1", "1.2.3"),
+            (">=1.2.3 >=1.2.1", "1.2.3"),
+            (">=1.2.1 >=1.2.3", "1.2.3"),
+            (">=1.2", "1.2.8"),
+            ("^1.2.3", "1.8.1"),
+            ("^0.1.2", "0.1.2"),
+            ("^0.1", "0.1.2"),
+            ("^0.0.1", "0.0.1"),
+            ("^1.2", "1.4.2"),
+            ("^1.2 ^1", "1.4.2"),
+            ("^1.2.3-alpha", "1.2.3-pre"),
+            ("^1.2.0-alpha", "1.2.0-pre"),
+            ("^0.0.1-alpha", "0.0.1-beta"),
+            ("^0.0.1-alpha", "0.0.1"),
+            ("^0.1.1-alpha", "0.1.1-beta"),
+            ("^x", "1.2.3"),
+            ("x - 1.0.0", "0.9.7"),
+            ("x - 1.x", "0.9.7"),
+            ("1.0.0 - x", "1.9.7"),
+            ("1.x - x", "1.9.7"),
+            ("<=7.x", "7.9.9"),
+        ] {
+            assert!(range(input).satisfies(&version(ver)), "{ver} in {input}");
+        }
+    }
+
+    #[test]
+    fn excludes() {
+        for (input, ver) in [
+            ("1.0.0 - 2.0.0", "2.2.3"),
+            ("1.2.3+asdf - 2.4.3+asdf", "1.2.3-pre.2"),
+            ("1.2.3+asdf - 2.4.3+asdf", "2.4.3-alpha"),
+            ("^1.2.3+build", "2.0.0"),
+            ("^1.2.3+build", "1.2.0"),
+            ("^1.2.3", "1.2.3-pre"),
+            ("^1.2", "1.2.0-pre"),
+            (">1.2", "1.3.0-beta"),
+            ("<=1.2.3", "1.2.3-beta"),
+            ("^1.2.3", "1.2.3-beta"),
+            ("=0.7.x", "0.7.0-asdf"),
+            (">=0.7.x", "0.7.0-asdf"),
+            ("<=0.7.x", "0.7.0-asdf"),
+            ("1", "1.0.0beta"),
+            ("<1", "1.0.0beta"),
+            ("< 1", "1.0.0beta"),
+            ("1.0.0", "1.0.1"),
+            (">=1.0.0", "0.0.0"),
+            (">=1.0.0", "0.0.1"),
+            (">=1.0.0", "0.1.0"),
+            (">1.0.0", "0.0.1"),
+            (">1.0.0", "0.1.0"),
+            ("<=2.0.0", "3.0.0"),
+            ("<=2.0.0", "2.9999.9999"),
+            ("<=2.0.0", "2.2.9"),
+            ("<2.0.0", "2.9999.9999"),
+            ("<2.0.0", "2.2.9"),
+            (">=0.1.97", "0.1.93"),
+            ("0.1.20 || 1.2.4", "1.2.3"),
+            (">=0.2.3 || <0.0.1", "0.0.3"),
+            (">=0.2.3 || <0.0.1", "0.2.2"),
+            ("2.x.x", "1.1.3"),
+            ("2.x.x", "3.1.3"),
+            ("1.2.x", "1.3.3"),
+            ("1.2.x || 2.x", "3.1.3"),
+            ("1.2.x || 2.x", "1.1.3"),
+            ("2.*.*", "1.1.3"),
+            ("2.*.*", "3.1.3"),
+            ("2", "1.1.2"),
+            ("2.3", "2.4.1"),
+            ("~0.0.1", "0.1.0-alpha"),
+            ("~0.0.1", "0.1.0"),
+            ("~2.4", "2.5.0"),
+            ("~2.4", "2.3.9"),
+            ("~>3.2.1", "3.3.2"),
+            ("~>3.2.1", "3.2.0"),
+            ("~1", "0.2.3"),
+            ("~>1", "2.2.3"),
+            ("~1.0", "1.1.0"),
+            ("<1", "1.0.0"),
+            (">=1.2", "1.1.1"),
+            ("~v0.5.4-beta", "0.5.4-alpha"),
+            ("=0.7.x", "0.8.2"),
+            (">=0.7.x", "0.6.2"),
+            ("<0.7.x", "0.7.2"),
+            ("<1.2.3", "1.2.3-beta"),
+            ("=1.2.3", "1.2.3-beta"),
+            (">1.2", "1.2.8"),
+            ("^0.0.1", "0.0.2-alpha"),
+            ("^0.0.1", "0.0.2"),
+            ("^1.2.3", "2.0.0-alpha"),
+            ("^1.2.3", "1.2.2"),
+            ("^1.2", "1.1.9"),
+            ("*", "1.2.3-foo"),
+            ("^1.0.0", "2.0.0-rc1"),
+            ("1 - 2", "2.0.0-pre"),
+            ("1 - 2", "1.0.0-pre"),
+            ("1.1.x", "1.0.0-a"),
+            ("1.1.x", "1.1.0-a"),
+            ("1.1.x", "1.2.0-a"),
+            ("1.x", "1.0.0-a"),
+            ("1.x", "1.1.0-a"),
+            ("1.x", "1.2.0-a"),
+            (">=1.0.0 <1.1.0", "1.1.0"),
+            (">=1.0.0 <1.1.0", "1.1.0-pre"),
+            (">=1.0.0 <1.1.0-pre", "1.1.0-pre"),
+        ] {
+            let ver = match Version::parse(ver) {
+                Ok(ver) => ver,
+                Err(_) => continue,
+            };
+
+            assert!(!range(input).satisfies(&ver), "{ver} not in {input}");
+        }
+    }
+}
+
+mod max_satisfying {
+    use super::*;
+
+    #[test]
+    fn returns_highest_match() {
+        let versions = ["1.2.3", "1.2.4", "1.3.0-beta", "2.0.0"]
+            .into_iter()
+            .map(version)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            range("~1.2").max_satisfying(&versions),
+            Some(&version("1.2.4"))
+        );
+        assert_eq!(
+            range("^1.2.3").max_satisfying(&versions),
+            Some(&version("1.2.4"))
+        );
+        assert_eq!(
+            range("*").max_satisfying(&versions),
+            Some(&version("2.0.0"))
+        );
+        assert_eq!(range("^3").max_satisfying(&versions), None);
+    }
+}
+
+mod min_version {
+    use super::*;
+
+    #[test]
+    fn returns_lowest_version() {
+        for (input, expected) in [
+            ("*", Some("0.0.0")),
+            ("* || >=2", Some("0.0.0")),
+            (">=1.0.0", Some("1.0.0")),
+            (">1.0.0", Some("1.0.1")),
+            (">1.0.0-0", Some("1.0.0-0.0")),
+            (">1.0.0-beta", Some("1.0.0-beta.0")),
+            ("^1.2.3", Some("1.2.3")),
+            ("^1.2.3-beta", Some("1.2.3-beta")),
+            ("~1.2", Some("1.2.0")),
+            ("1.x", Some("1.0.0")),
+            (">2 || >1.0.0", Some("1.0.1")),
+            ("<1.0.0", Some("0.0.0")),
+            ("<0.0.0-beta", Some("0.0.0-0")),
+            (">4 <3", None),
+            ("<0.0.0", None),
+        ] {
+            assert_eq!(range(input).min_version(), expected.map(version), "{input}");
+        }
+    }
+}
+
+mod intersects {
+    use super::*;
+
+    #[test]
+    fn detects_overlap() {
+        for (a, b, expected) in [
+            ("1.3.0 || <1.0.0 >2.0.0", "1.3.0 || <1.0.0 >2.0.0", true),
+            ("<1.0.0 >2.0.0", ">0.0.0", false),
+            (">0.0.0", "<1.0.0 >2.0.0", false),
+            ("<1.0.0 >2.0.0", ">1.4.0 <1.6.0", false),
+            ("<1.0.0 >2.0.0", ">1.4.0 <1.6.0 || 2.0.0", false),
+            (">1.0.0 <=2.0.0", "2.0.0", true),
+            ("<1.0.0 >=2.0.0", "2.1.0", false),
+            ("<1.0.0 >=2.0.0", ">1.4.0 <1.6.0 || 2.0.0", false),
+            ("1.5.x", "<1.5.0 || >=1.6.0", false),
+            ("<1.5.0 || >=1.6.0", "1.5.x", false),
+            ("1.0.0 - 2.0.0", "1.0.0 - 2.0.0", true),
+            ("1.0.0 - 2.0.0", "2.0.0 - 3.0.0", true),
+            ("1.0.0 - 2.0.0", "2.0.1 - 3.0.0", false),
+            ("^1.2", "~1.4", true),
+            ("^1.2", "^2", false),
+            ("*", "0.0.1", true),
+            ("*", ">=1.0.0", true),
+            (">=18", "^16 || ^18", true),
+            (">=18", "^16", false),
+            ("<0.0.0", "<1.0.0", false),
+            ("<0.0.0-0", "<1.0.0", false),
+            ("<0.0.0-beta", "*", true),
+            ("<0.0.0-beta", "<1.0.0", false),
+        ] {
+            assert_eq!(range(a).intersects(&range(b)), expected, "{a} ∩ {b}");
+        }
+    }
+}
+
+mod subset {
+    use super::*;
+
+    #[test]
+    fn detects_subsets() {
+        for (sub, dom, expected) in [
+            ("1.2.3", "1.2.3", true),
+            ("1.2.3", "1.x", true),
+            ("1.2.3 1.2.4", "1.2.3", true),
+            ("1.2.3 1.2.4", "1.2.9", true),
+            ("1.2.3", ">1.2.0", true),
+            ("1.2.3 2.3.4 || 2.3.4", "3", false),
+            ("^1.2.3-pre.0", "1.x", false),
+            ("^1.2.3-pre.0", ">=1.0.0-0", false),
+            ("^1.2.3-pre.0", ">=1.2.3-0", true),
+            (">2 <1", "3", true),
+            ("1 || 2 || 3", ">=1.0.0", true),
+            ("*", "*", true),
+            ("", "*", true),
+            ("*", "", true),
+            ("*", ">=0.0.0", true),
+            ("^2 || ^3 || ^4", ">=1", true),
+            ("^2 || ^3 || ^4", ">1", true),
+            ("^2 || ^3 || ^4", ">=2", true),
+            ("^2 || ^3 || ^4", ">2", false),
+            ("~1.2.3", "^1.2.0", true),
+            ("^1.2.3", "~1.2.0", false),
+            (">=1.2.3", "*", true),
+            ("<1.2.3", "*", false),
+            (">=1.2.3", ">=1.0.0", true),
+            (">=1.0.0", ">=1.2.3", false),
+            ("2.x", "^1", false),
+            ("1.x", "1.2.3 - 2", false),
+            ("<1.0.0", ">=1.0.0", false),
+        ] {
+            assert_eq!(range(sub).subset(&range(dom)), expected, "{sub} ⊂ {dom}");
+        }
+    }
+}
+
+mod intersect {
+    use super::*;
+
+    fn intersect(a: &str, b: &str) -> Option<String> {
+        range(a).intersect(&range(b)).map(|range| range.to_string())
+    }
+
+    #[test]
+    fn returns_tightest_range() {
+        assert_eq!(intersect(">=16", ">=18.12"), Some(">=18.12.0".into()));
+        assert_eq!(
+            intersect("^18.0.0", ">=18.19.0"),
+            Some(">=18.19.0 <19.0.0-0".into())
+        );
+        assert_eq!(intersect("*", "~1.2.3"), Some(">=1.2.3 <1.3.0-0".into()));
+        assert_eq!(intersect("*", "*"), Some("*".into()));
+        assert_eq!(intersect(">=1.0.0", "<=1.0.0"), Some("1.0.0".into()));
+        assert_eq!(intersect("1.2.3", ">=1"), Some("1.2.3".into()));
+    }
+
+    #[test]
+    fn supports_unions() {
+        assert_eq!(
+            intersect("^16 || ^18 || >=20", ">=18"),
+            Some(">=18.0.0 <19.0.0-0 || >=20.0.0".into())
+        );
+        assert_eq!(
+            intersect("^16 || ^18", "^18 || ^20"),
+            Some(">=18.0.0 <19.0.0-0".into())
+        );
+    }
+
+    #[test]
+    fn returns_none_when_disjoint() {
+        assert_eq!(intersect("^16", ">=18"), None);
+        assert_eq!(intersect(">1.0.0", "<=1.0.0"), None);
+        assert_eq!(intersect("1.2.3", "1.2.4"), None);
+        assert_eq!(intersect("^1 || ^2", "^3"), None);
+    }
+
+    #[test]
+    fn result_matches_both() {
+        let a = range("^16.14 || >=18.12 <21");
+        let b = range(">=17 <20.5");
+        let both = a.intersect(&b).unwrap();
+
+        for version in [
+            "16.20.0", "17.0.0", "18.12.0", "19.9.9", "20.4.0", "20.5.0", "21.0.0",
+        ] {
+            let version = Version::parse(version).unwrap();
+
+            assert_eq!(
+                both.satisfies(&version),
+                a.satisfies(&version) && b.satisfies(&version),
+                "{version}"
+            );
+        }
+    }
+}
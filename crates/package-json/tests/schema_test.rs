@@ -0,0 +1,118 @@
+use nodejs_package_json::{PackageJson, PackageManagerField, VersionProtocol, WorkspaceProtocol};
+use regex::Regex;
+use schemars::schema_for;
+use serde_json::Value;
+use std::str::FromStr;
+
+fn generate() -> Value {
+    serde_json::to_value(schema_for!(PackageJson)).unwrap()
+}
+
+fn get_pattern(schema: &Value, name: &str) -> Regex {
+    Regex::new(schema["definitions"][name]["pattern"].as_str().unwrap()).unwrap()
+}
+
+#[test]
+fn generates_package_json_schema() {
+    let schema = generate();
+
+    assert_eq!(schema["title"], "PackageJson");
+
+    for key in [
+        "name",
+        "version",
+        "dependencies",
+        "exports",
+        "packageManager",
+        "publishConfig",
+        "sideEffects",
+        "typesVersions",
+        "workspaces",
+    ] {
+        assert!(schema["properties"].get(key).is_some(), "{key}");
+    }
+}
+
+#[test]
+fn uses_strings_for_parsed_types() {
+    let schema = generate();
+
+    for name in ["Range", "VersionProtocol"] {
+        assert_eq!(schema["definitions"][name]["type"], "string", "{name}");
+    }
+
+    // Raw values are kept as-is, so any string is accepted
+    assert_eq!(
+        schema["properties"]["packageManager"]["type"],
+        serde_json::json!(["string", "null"])
+    );
+
+    // Untagged shapes are described by their raw values
+    assert!(schema["definitions"]["Person"]["anyOf"].is_array());
+    assert!(schema["definitions"]["RepositoryField"]["anyOf"].is_array());
+}
+
+#[test]
+fn version_protocol_pattern_matches_parser() {
+    let pattern = get_pattern(&generate(), "VersionProtocol");
+
+    for value in [
+        "",
+        "*",
+        "1.2.3",
+        "^1.2.3",
+        ">=1 <2",
+        "1.x || 2.x",
+        "latest",
+        "owner/repo#main",
+        "npm:pkg@^1",
+        "jsr:@scope/pkg@1",
+        "catalog:",
+        "file:../pkg",
+        "git+https://github.com/owner/repo.git#v1",
+        "https://example.com/pkg.tgz",
+        "patch:pkg@npm%3A1.0.0#./patches/pkg.patch",
+        "workspace:^",
+        "github:owner/repo",
+        "gitlab:owner/repo",
+        "bitbucket:owner/repo#v1",
+        "owner/repo#semver:^1.0",
+        "link:../pkg",
+        "portal:../pkg",
+        "exec:./build.js",
+        "1.2.3-beta.1",
+        "next",
+    ] {
+        assert!(VersionProtocol::from_str(value).is_ok(), "{value}");
+        assert!(pattern.is_match(value), "{value}");
+    }
+
+    for value in ["not a valid/version/at all", "unknown:value", "a b"] {
+        assert!(VersionProtocol::from_str(value).is_err(), "{value}");
+        assert!(!pattern.is_match(value), "{value}");
+    }
+}
+
+#[test]
+fn workspace_protocol_pattern_matches_parser() {
+    let schema = serde_json::to_value(schema_for!(WorkspaceProtocol)).unwrap();
+    let pattern = Regex::new(schema["pattern"].as_str().unwrap()).unwrap();
+
+    for value in ["*", "^", "~", "^1.2.3", "1.2.3", "alias@*", "../path"] {
+        assert!(WorkspaceProtocol::from_str(value).is_ok(), "{value}");
+        assert!(pattern.is_match(value), "{value}");
+    }
+
+    assert!(!pattern.is_match("latest"));
+}
+
+#[test]
+fn package_manager_pattern_matches_parser() {
+    let schema = serde_json::to_value(schema_for!(PackageManagerField)).unwrap();
+    let pattern = Regex::new(schema["pattern"].as_str().unwrap()).unwrap();
+
+    assert!(pattern.is_match("pnpm@9.1.0"));
+    assert!(pattern.is_match("yarn@4.0.0-rc.1+sha224.abc123"));
+    assert!(!pattern.is_match("pnpm"));
+    assert!(!pattern.is_match("deno@1.0.0"));
+}
@@ -0,0 +1,78 @@
+use nodejs_package_json::{PackageJson, SideEffectsError, SideEffectsField};
+
+fn parse(content: &str) -> PackageJson {
+    serde_json::from_str(content).unwrap()
+}
+
+#[test]
+fn parses_field() {
+    assert_eq!(
+        parse(r#"{ "sideEffects": false }"#).side_effects.unwrap(),
+        SideEffectsField::Bool(false)
+    );
+    assert_eq!(
+        parse(r#"{ "sideEffects": ["*.css"] }"#)
+            .side_effects
+            .unwrap(),
+        SideEffectsField::List(vec!["*.css".into()])
+    );
+    assert_eq!(parse(r#"{ "sideEffects": "yes" }"#).side_effects, None);
+}
+
+#[test]
+fn defaults_to_side_effects() {
+    assert!(parse("{}").has_side_effects("index.js").unwrap());
+}
+
+#[test]
+fn supports_bools() {
+    let pkg = parse(r#"{ "sideEffects": false }"#);
+
+    assert!(!pkg.has_side_effects("index.js").unwrap());
+
+    let pkg = parse(r#"{ "sideEffects": true }"#);
+
+    assert!(pkg.has_side_effects("index.js").unwrap());
+}
+
+#[test]
+fn matches_patterns_without_slash_in_any_dir() {
+    let pkg = parse(r#"{ "sideEffects": ["*.css", "polyfill.js"] }"#);
+
+    assert!(pkg.has_side_effects("style.css").unwrap());
+    assert!(pkg.has_side_effects("./src/components/button.css").unwrap());
+    assert!(pkg.has_side_effects("lib/polyfill.js").unwrap());
+    assert!(!pkg.has_side_effects("lib/index.js").unwrap());
+    assert!(!pkg.has_side_effects("lib/not-polyfill.js").unwrap());
+}
+
+#[test]
+fn matches_patterns_with_slash_from_root() {
+    let pkg = parse(r#"{ "sideEffects": ["./src/setup/*.js", "dist/**/*.mjs"] }"#);
+
+    assert!(pkg.has_side_effects("src/setup/env.js").unwrap());
+    assert!(!pkg.has_side_effects("src/setup/nested/env.js").unwrap());
+    assert!(!pkg.has_side_effects("lib/src/setup/env.js").unwrap());
+    assert!(pkg.has_side_effects("dist/index.mjs").unwrap());
+    assert!(pkg.has_side_effects("dist/a/b/index.mjs").unwrap());
+}
+
+#[test]
+fn reuses_matcher() {
+    let matcher = SideEffectsField::List(vec!["*.css".into()])
+        .to_matcher()
+        .unwrap();
+
+    assert!(matcher.is_side_effectful("a.css"));
+    assert!(!matcher.is_side_effectful("a.js"));
+}
+
+#[test]
+fn errors_for_invalid_globs() {
+    let pkg = parse(r#"{ "sideEffects": ["src/[invalid"] }"#);
+
+    assert!(matches!(
+        pkg.has_side_effects("index.js").unwrap_err(),
+        SideEffectsError::InvalidGlob { glob, .. } if glob == "src/[invalid"
+    ));
+}
@@ -0,0 +1,227 @@
+use nodejs_package_json::{to_declaration_path, PackageJson, Version};
+use std::path::PathBuf;
+
+fn parse(content: &str) -> PackageJson {
+    serde_json::from_str(content).unwrap()
+}
+
+fn ts(version: &str) -> Version {
+    Version::parse(version).unwrap()
+}
+
+fn resolve(pkg: &PackageJson, subpath: &str, version: &str) -> Option<PathBuf> {
+    pkg.resolve_types(subpath, &ts(version), &["import"])
+}
+
+#[test]
+fn converts_to_declaration_paths() {
+    assert_eq!(to_declaration_path("index.js"), PathBuf::from("index.d.ts"));
+    assert_eq!(
+        to_declaration_path("index.tsx"),
+        PathBuf::from("index.d.ts")
+    );
+    assert_eq!(
+        to_declaration_path("index.mjs"),
+        PathBuf::from("index.d.mts")
+    );
+    assert_eq!(
+        to_declaration_path("index.cts"),
+        PathBuf::from("index.d.cts")
+    );
+    assert_eq!(
+        to_declaration_path("index.d.ts"),
+        PathBuf::from("index.d.ts")
+    );
+    assert_eq!(
+        to_declaration_path("lib/sub"),
+        PathBuf::from("lib/sub.d.ts")
+    );
+}
+
+#[test]
+fn parses_fields() {
+    let pkg = parse(
+        r#"{
+            "types": "./index.d.ts",
+            "typings": "./typings.d.ts",
+            "typesVersions": { ">=4.0": { "*": ["ts4/*"] } }
+        }"#,
+    );
+
+    assert_eq!(pkg.types.unwrap(), PathBuf::from("./index.d.ts"));
+    assert_eq!(pkg.typings.unwrap(), PathBuf::from("./typings.d.ts"));
+    assert_eq!(
+        pkg.types_versions.unwrap()[">=4.0"]["*"],
+        vec!["ts4/*".to_owned()]
+    );
+
+    let pkg = parse(r#"{ "types": 123, "typesVersions": [] }"#);
+
+    assert!(pkg.types.is_none());
+    assert!(pkg.types_versions.is_none());
+}
+
+mod legacy {
+    use super::*;
+
+    #[test]
+    fn uses_types_then_typings_then_main() {
+        assert_eq!(
+            resolve(
+                &parse(r#"{ "types": "./a.d.ts", "typings": "./b.d.ts" }"#),
+                ".",
+                "5.0.0"
+            ),
+            Some(PathBuf::from("a.d.ts"))
+        );
+        assert_eq!(
+            resolve(&parse(r#"{ "typings": "./b.d.ts" }"#), ".", "5.0.0"),
+            Some(PathBuf::from("b.d.ts"))
+        );
+        assert_eq!(
+            resolve(&parse(r#"{ "main": "./lib/index.js" }"#), ".", "5.0.0"),
+            Some(PathBuf::from("lib/index.d.ts"))
+        );
+        assert_eq!(
+            resolve(&parse("{}"), ".", "5.0.0"),
+            Some(PathBuf::from("index.d.ts"))
+        );
+    }
+
+    #[test]
+    fn resolves_subpaths() {
+        assert_eq!(
+            resolve(&parse("{}"), "./lib/util", "5.0.0"),
+            Some(PathBuf::from("lib/util.d.ts"))
+        );
+        assert_eq!(
+            resolve(&parse("{}"), "lib/util.js", "5.0.0"),
+            Some(PathBuf::from("lib/util.d.ts"))
+        );
+    }
+
+    #[test]
+    fn resolves_dot_prefixed_subpaths() {
+        let pkg = parse("{}");
+
+        assert_eq!(
+            resolve(&pkg, "./.hidden", "5.0.0"),
+            Some(PathBuf::from(".hidden.d.ts"))
+        );
+        assert_eq!(
+            resolve(&pkg, ".foo", "5.0.0"),
+            Some(PathBuf::from(".foo.d.ts"))
+        );
+        assert_eq!(
+            resolve(&pkg, "./.config/index.js", "5.0.0"),
+            Some(PathBuf::from(".config/index.d.ts"))
+        );
+        assert_eq!(
+            resolve(&pkg, "./", "5.0.0"),
+            Some(PathBuf::from("index.d.ts"))
+        );
+        assert_eq!(resolve(&pkg, "../x", "5.0.0"), None);
+        assert_eq!(resolve(&pkg, "..", "5.0.0"), None);
+    }
+
+    #[test]
+    fn applies_types_versions() {
+        let pkg = parse(
+            r#"{
+                "types": "./index.d.ts",
+                "typesVersions": {
+                    "<4.0": { "*": ["ts3/*"] },
+                    ">=4.0": { "index.d.ts": ["./ts4/main.d.ts"], "utils/*": ["ts4/utils/*"] }
+                }
+            }"#,
+        );
+
+        assert_eq!(
+            resolve(&pkg, ".", "3.9.0"),
+            Some(PathBuf::from("ts3/index.d.ts"))
+        );
+        assert_eq!(
+            resolve(&pkg, ".", "5.0.0"),
+            Some(PathBuf::from("ts4/main.d.ts"))
+        );
+        assert_eq!(
+            resolve(&pkg, "./utils/fs", "5.0.0"),
+            Some(PathBuf::from("ts4/utils/fs.d.ts"))
+        );
+        assert_eq!(
+            resolve(&pkg, "./other", "5.0.0"),
+            Some(PathBuf::from("other.d.ts"))
+        );
+    }
+
+    #[test]
+    fn selects_first_matching_types_versions() {
+        let pkg = parse(
+            r#"{ "typesVersions": { ">=5.0": { "*": ["ts5/*"] }, "*": { "*": ["all/*"] } } }"#,
+        );
+
+        assert_eq!(
+            pkg.get_types_versions_paths(&ts("5.1.0")).unwrap()["*"],
+            vec!["ts5/*".to_owned()]
+        );
+        assert_eq!(
+            pkg.get_types_versions_paths(&ts("4.9.0")).unwrap()["*"],
+            vec!["all/*".to_owned()]
+        );
+    }
+}
+
+mod exports {
+    use super::*;
+
+    #[test]
+    fn uses_types_condition() {
+        let pkg = parse(
+            r#"{
+                "types": "./ignored.d.ts",
+                "exports": {
+                    ".": { "types": "./dist/index.d.ts", "default": "./dist/index.js" },
+                    "./utils": { "import": "./dist/utils.mjs", "require": "./dist/utils.cjs" }
+                }
+            }"#,
+        );
+
+        assert_eq!(
+            resolve(&pkg, ".", "5.0.0"),
+            Some(PathBuf::from("dist/index.d.ts"))
+        );
+        assert_eq!(
+            resolve(&pkg, "./utils", "5.0.0"),
+            Some(PathBuf::from("dist/utils.d.mts"))
+        );
+        assert_eq!(
+            pkg.resolve_types("./utils", &ts("5.0.0"), &["require"]),
+            Some(PathBuf::from("dist/utils.d.cts"))
+        );
+        assert_eq!(resolve(&pkg, "./missing", "5.0.0"), None);
+    }
+
+    #[test]
+    fn uses_versioned_types_conditions() {
+        let pkg = parse(
+            r#"{
+                "exports": {
+                    ".": {
+                        "types@<4.7": "./ts4.6/index.d.ts",
+                        "types": "./index.d.ts",
+                        "default": "./index.js"
+                    }
+                }
+            }"#,
+        );
+
+        assert_eq!(
+            resolve(&pkg, ".", "4.6.0"),
+            Some(PathBuf::from("ts4.6/index.d.ts"))
+        );
+        assert_eq!(
+            resolve(&pkg, ".", "5.0.0"),
+            Some(PathBuf::from("index.d.ts"))
+        );
+    }
+}
@@ -0,0 +1,265 @@
+use nodejs_package_json::{validate_package_name, PackageJson, ValidationRule, ValidationSeverity};
+
+fn validate(content: &str) -> Vec<(ValidationRule, String)> {
+    PackageJson::validate(content)
+        .diagnostics
+        .into_iter()
+        .map(|diag| (diag.rule, diag.pointer))
+        .collect()
+}
+
+#[test]
+fn passes_valid_manifest() {
+    let report = PackageJson::validate(
+        r#"{
+  "name": "@scope/pkg",
+  "dependencies": { "a": "^1.0.0" },
+  "devDependencies": { "b": "workspace:*", "react": "^18.0.0" },
+  "peerDependencies": { "react": ">=17" },
+  "exports": { ".": { "import": "./index.mjs", "default": "./index.js" } }
+}"#,
+    );
+
+    assert!(report.is_empty());
+    assert!(!report.has_errors());
+}
+
+#[test]
+fn reports_invalid_json() {
+    let content = "{\n  \"name\": \"pkg\",\n}";
+    let report = PackageJson::validate(content);
+
+    assert_eq!(report.diagnostics.len(), 1);
+    assert_eq!(report.diagnostics[0].rule, ValidationRule::InvalidJson);
+    assert_eq!(report.diagnostics[0].span, Some((content.len() - 1, 0)));
+    assert_eq!(
+        validate("[]"),
+        vec![(ValidationRule::InvalidJson, "".into())]
+    );
+}
+
+#[test]
+fn reports_invalid_names() {
+    for name in [
+        "",
+        " pkg",
+        ".pkg",
+        "_pkg",
+        "node_modules",
+        "@scope",
+        "@scope/",
+        "pkg name",
+        "pkg:name",
+    ] {
+        let diagnostics = validate(&format!(r#"{{ "name": "{name}" }}"#));
+
+        assert!(!diagnostics.is_empty(), "{name}");
+        assert!(
+            diagnostics
+                .iter()
+                .all(|diag| *diag == (ValidationRule::InvalidName, "/name".into())),
+            "{name}"
+        );
+    }
+
+    assert_eq!(
+        validate(r#"{ "name": 123 }"#),
+        vec![(ValidationRule::InvalidName, "/name".into())]
+    );
+}
+
+#[test]
+fn reports_legacy_names_as_warnings() {
+    for name in ["JSONStream", "pkg~", "@scope/pkg!", "http"] {
+        let report = PackageJson::validate(format!(r#"{{ "name": "{name}" }}"#));
+
+        assert!(!report.has_errors(), "{name}");
+        assert_eq!(report.diagnostics.len(), 1, "{name}");
+        assert_eq!(report.diagnostics[0].rule, ValidationRule::LegacyName);
+        assert_eq!(report.diagnostics[0].severity, ValidationSeverity::Warning);
+    }
+}
+
+#[test]
+fn validates_names_for_new_and_old_packages() {
+    let result = validate_package_name("some-package");
+
+    assert!(result.is_valid_for_new_packages());
+    assert!(result.is_valid_for_old_packages());
+
+    let result = validate_package_name("JSONStream");
+
+    assert!(!result.is_valid_for_new_packages());
+    assert!(result.is_valid_for_old_packages());
+    assert_eq!(
+        result.warnings,
+        vec!["Name cannot contain uppercase letters."]
+    );
+
+    let result = validate_package_name("~pkg");
+
+    assert!(!result.is_valid_for_new_packages());
+    assert!(result.is_valid_for_old_packages());
+
+    let result = validate_package_name("a".repeat(215).as_str());
+
+    assert!(!result.is_valid_for_new_packages());
+    assert!(result.is_valid_for_old_packages());
+
+    let result = validate_package_name(" Pkg");
+
+    assert!(!result.is_valid_for_old_packages());
+    assert_eq!(result.errors.len(), 2);
+    assert_eq!(result.warnings.len(), 1);
+}
+
+#[test]
+fn returns_parsed_manifest() {
+    let report = PackageJson::validate(
+        r#"{ "name": "Pkg", "version": "1.0.0", "license": 123, "dependencies": { "a": "^1" } }"#,
+    );
+    let manifest = report.manifest.unwrap();
+
+    assert_eq!(manifest.name.as_deref(), Some("Pkg"));
+    assert_eq!(manifest.license, None);
+    assert!(manifest.dependencies.unwrap().contains_key("a"));
+
+    assert_eq!(PackageJson::validate("[]").manifest, None);
+}
+
+#[test]
+fn returns_manifest_without_invalid_entries() {
+    let report = PackageJson::validate(
+        r#"{ "name": "pkg", "version": "one", "dependencies": { "x": ">=1.2.3 <", "y": "^1" } }"#,
+    );
+
+    assert_eq!(
+        report
+            .diagnostics
+            .iter()
+            .map(|diag| (diag.rule, diag.pointer.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (ValidationRule::InvalidField, "/version"),
+            (ValidationRule::InvalidDependencyVersion, "/dependencies/x"),
+        ]
+    );
+
+    let manifest = report.manifest.unwrap();
+    let deps = manifest.dependencies.unwrap();
+
+    assert_eq!(manifest.name.as_deref(), Some("pkg"));
+    assert_eq!(manifest.version, None);
+    assert!(!deps.contains_key("x"));
+    assert!(deps.contains_key("y"));
+}
+
+#[test]
+fn reports_in_source_order() {
+    assert_eq!(
+        validate(
+            r#"{
+  "exports": "index.js",
+  "dependencies": { "b": "?", "a": "?" },
+  "name": ".pkg"
+}"#
+        ),
+        vec![
+            (ValidationRule::InvalidExportTarget, "/exports".into()),
+            (
+                ValidationRule::InvalidDependencyVersion,
+                "/dependencies/b".into()
+            ),
+            (
+                ValidationRule::InvalidDependencyVersion,
+                "/dependencies/a".into()
+            ),
+            (ValidationRule::InvalidName, "/name".into()),
+        ]
+    );
+}
+
+#[test]
+fn reports_invalid_versions_with_spans() {
+    let content = r#"{
+  "dependencies": {
+    "a": "^1.0.0",
+    "b": ">=1.2.3 <",
+    "c/d": 123
+  }
+}"#;
+    let report = PackageJson::validate(content);
+
+    assert_eq!(report.diagnostics.len(), 2);
+
+    let b = &report.diagnostics[0];
+    let (offset, length) = b.span.unwrap();
+
+    assert_eq!(b.rule, ValidationRule::InvalidDependencyVersion);
+    assert_eq!(b.pointer, "/dependencies/b");
+    assert_eq!(&content[offset..offset + length], r#"">=1.2.3 <""#);
+
+    let c = &report.diagnostics[1];
+    let (offset, length) = c.span.unwrap();
+
+    assert_eq!(c.pointer, "/dependencies/c~1d");
+    assert_eq!(&content[offset..offset + length], "123");
+}
+
+#[test]
+fn reports_dependency_warnings() {
+    let report = PackageJson::validate(
+        r#"{
+  "dependencies": { "a": "^1.0.0" },
+  "devDependencies": { "a": "^1.0.0", "b": "^1.0.0" },
+  "peerDependencies": { "b": "^1.0.0", "c": "^1.0.0" }
+}"#,
+    );
+
+    assert!(!report.has_errors());
+    assert!(report
+        .diagnostics
+        .iter()
+        .all(|diag| diag.severity == ValidationSeverity::Warning));
+    assert_eq!(
+        report
+            .diagnostics
+            .into_iter()
+            .map(|diag| (diag.rule, diag.pointer))
+            .collect::<Vec<_>>(),
+        vec![
+            (
+                ValidationRule::DuplicateDependency,
+                "/devDependencies/a".into()
+            ),
+            (
+                ValidationRule::PeerWithoutDevDependency,
+                "/peerDependencies/c".into()
+            )
+        ]
+    );
+}
+
+#[test]
+fn reports_invalid_exports() {
+    assert_eq!(
+        validate(
+            r#"{
+  "exports": {
+    ".": "index.js",
+    "./sub": { "import": ["./sub.mjs", "sub.js"], "./nested": "./nested.js" },
+    "require": "./index.cjs"
+  }
+}"#
+        ),
+        vec![
+            (ValidationRule::MixedExportKeys, "/exports".into()),
+            (ValidationRule::InvalidExportTarget, "/exports/.".into()),
+            (ValidationRule::MixedExportKeys, "/exports/.~1sub".into()),
+            (
+                ValidationRule::InvalidExportTarget,
+                "/exports/.~1sub/import/1".into()
+            ),
+        ]
+    );
+}
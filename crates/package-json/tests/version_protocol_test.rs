@@ -1,11 +1,11 @@
-use nodejs_package_json::VersionProtocol;
-use semver::VersionReq;
+use nodejs_package_json::{Range, VersionProtocol, WorkspaceProtocol};
+use semver::Version;
 use std::path::PathBuf;
 use std::str::FromStr;
 
 #[test]
 fn empty() {
-    let exp = VersionProtocol::Requirement(VersionReq::parse("*").unwrap());
+    let exp = VersionProtocol::Requirement(Range::parse("*").unwrap());
 
     assert_eq!(VersionProtocol::from_str("").unwrap(), exp);
     assert_eq!(exp.to_string(), "*")
@@ -13,12 +13,88 @@ fn empty() {
 
 #[test]
 fn star() {
-    let exp = VersionProtocol::Requirement(VersionReq::parse("*").unwrap());
+    let exp = VersionProtocol::Requirement(Range::parse("*").unwrap());
 
     assert_eq!(VersionProtocol::from_str("*").unwrap(), exp);
     assert_eq!(exp.to_string(), "*")
 }
 
+#[test]
+fn alias() {
+    let exp = VersionProtocol::Alias {
+        package: "react".into(),
+        requirement: Some(Box::new(VersionProtocol::Requirement(
+            Range::parse("^17.0.2").unwrap(),
+        ))),
+    };
+
+    assert_eq!(VersionProtocol::from_str("npm:react@^17.0.2").unwrap(), exp);
+    assert_eq!(exp.to_string(), "npm:react@^17.0.2")
+}
+
+#[test]
+fn alias_scoped() {
+    let exp = VersionProtocol::Alias {
+        package: "@scope/pkg".into(),
+        requirement: Some(Box::new(VersionProtocol::Version(Version::new(1, 2, 3)))),
+    };
+
+    assert_eq!(
+        VersionProtocol::from_str("npm:@scope/pkg@1.2.3").unwrap(),
+        exp
+    );
+    assert_eq!(exp.to_string(), "npm:@scope/pkg@1.2.3")
+}
+
+#[test]
+fn alias_without_version() {
+    let exp = VersionProtocol::Alias {
+        package: "@scope/pkg".into(),
+        requirement: None,
+    };
+
+    assert_eq!(VersionProtocol::from_str("npm:@scope/pkg").unwrap(), exp);
+    assert_eq!(exp.to_string(), "npm:@scope/pkg")
+}
+
+#[test]
+fn alias_tag() {
+    let exp = VersionProtocol::Alias {
+        package: "react".into(),
+        requirement: Some(Box::new(VersionProtocol::Tag("next".into()))),
+    };
+
+    assert_eq!(VersionProtocol::from_str("npm:react@next").unwrap(), exp);
+    assert_eq!(exp.to_string(), "npm:react@next")
+}
+
+#[test]
+fn catalog() {
+    let exp = VersionProtocol::Catalog(None);
+
+    assert_eq!(VersionProtocol::from_str("catalog:").unwrap(), exp);
+    assert_eq!(exp.to_string(), "catalog:")
+}
+
+#[test]
+fn catalog_named() {
+    let exp = VersionProtocol::Catalog(Some("react18".into()));
+
+    assert_eq!(VersionProtocol::from_str("catalog:react18").unwrap(), exp);
+    assert_eq!(exp.to_string(), "catalog:react18")
+}
+
+#[test]
+fn exec() {
+    let exp = VersionProtocol::Exec(PathBuf::from("./scripts/gen.js"));
+
+    assert_eq!(
+        VersionProtocol::from_str("exec:./scripts/gen.js").unwrap(),
+        exp
+    );
+    assert_eq!(exp.to_string(), "exec:./scripts/gen.js")
+}
+
 #[test]
 fn file() {
     let exp = VersionProtocol::File(PathBuf::from("../dir"));
@@ -132,6 +208,28 @@ fn https_url() {
     assert_eq!(exp.to_string(), "https://domain.com/dep.tgz")
 }
 
+#[test]
+fn jsr() {
+    let exp = VersionProtocol::Jsr {
+        package: Some("@std/path".into()),
+        requirement: Range::parse("^1").unwrap(),
+    };
+
+    assert_eq!(VersionProtocol::from_str("jsr:@std/path@^1").unwrap(), exp);
+    assert_eq!(exp.to_string(), "jsr:@std/path@^1")
+}
+
+#[test]
+fn jsr_without_package() {
+    let exp = VersionProtocol::Jsr {
+        package: None,
+        requirement: Range::parse("^1.2").unwrap(),
+    };
+
+    assert_eq!(VersionProtocol::from_str("jsr:^1.2").unwrap(), exp);
+    assert_eq!(exp.to_string(), "jsr:^1.2")
+}
+
 #[test]
 fn link() {
     let exp = VersionProtocol::Link(PathBuf::from("../dir"));
@@ -140,6 +238,70 @@ fn link() {
     assert_eq!(exp.to_string(), "link:../dir")
 }
 
+#[test]
+fn patch() {
+    let exp = VersionProtocol::Patch {
+        package: "lodash".into(),
+        requirement: Box::new(VersionProtocol::Version(Version::new(4, 17, 21))),
+        paths: vec![PathBuf::from("./patches/lodash.patch")],
+        params: None,
+    };
+
+    assert_eq!(
+        VersionProtocol::from_str("patch:lodash@npm%3A4.17.21#./patches/lodash.patch").unwrap(),
+        exp
+    );
+    assert_eq!(
+        exp.to_string(),
+        "patch:lodash@npm%3A4.17.21#./patches/lodash.patch"
+    )
+}
+
+#[test]
+fn patch_scoped_with_params() {
+    let exp = VersionProtocol::Patch {
+        package: "@scope/pkg".into(),
+        requirement: Box::new(VersionProtocol::Requirement(
+            Range::parse("^1.0.0").unwrap(),
+        )),
+        paths: vec![
+            PathBuf::from("~/.yarn/patches/a.patch"),
+            PathBuf::from("./b#1.patch"),
+        ],
+        params: Some("version=1.0.0&hash=abc123".into()),
+    };
+
+    assert_eq!(
+        VersionProtocol::from_str(
+            "patch:@scope/pkg@npm%3A^1.0.0#~/.yarn/patches/a.patch&./b%231.patch::version=1.0.0&hash=abc123"
+        )
+        .unwrap(),
+        exp
+    );
+    assert_eq!(
+        exp.to_string(),
+        "patch:@scope/pkg@npm%3A^1.0.0#~/.yarn/patches/a.patch&./b%231.patch::version=1.0.0&hash=abc123"
+    )
+}
+
+#[test]
+fn patch_wrapping_protocol() {
+    let exp = VersionProtocol::Patch {
+        package: "pkg".into(),
+        requirement: Box::new(VersionProtocol::Workspace(WorkspaceProtocol::Any {
+            alias: None,
+        })),
+        paths: vec![PathBuf::from("./pkg.patch")],
+        params: None,
+    };
+
+    assert_eq!(
+        VersionProtocol::from_str("patch:pkg@workspace%3A*#./pkg.patch").unwrap(),
+        exp
+    );
+    assert_eq!(exp.to_string(), "patch:pkg@workspace%3A*#./pkg.patch")
+}
+
 #[test]
 fn portal() {
     let exp = VersionProtocol::Portal(PathBuf::from("../dir"));
@@ -148,10 +310,96 @@ fn portal() {
     assert_eq!(exp.to_string(), "portal:../dir")
 }
 
+#[test]
+fn tag() {
+    for tag in ["latest", "next", "beta", "canary-2024"] {
+        let exp = VersionProtocol::Tag(tag.into());
+
+        assert_eq!(VersionProtocol::from_str(tag).unwrap(), exp);
+        assert_eq!(exp.to_string(), tag)
+    }
+}
+
+#[test]
+fn errors_for_invalid_value() {
+    assert!(VersionProtocol::from_str("not a tag").is_err());
+    assert!(VersionProtocol::from_str(">=1.2.3 <").is_err());
+}
+
 #[test]
 fn range() {
-    let exp = VersionProtocol::Requirement(VersionReq::parse(">=1.2.3, <=4.5.6").unwrap());
+    let exp = VersionProtocol::Requirement(Range::parse("1.2.3 - 4.5.6").unwrap());
 
     assert_eq!(VersionProtocol::from_str("1.2.3 - 4.5.6").unwrap(), exp);
-    assert_eq!(exp.to_string(), ">=1.2.3, <=4.5.6")
+    assert_eq!(exp.to_string(), "1.2.3 - 4.5.6")
+}
+
+#[test]
+fn range_with_spaces() {
+    let exp = VersionProtocol::Requirement(Range::parse(">=1.2.3 <2.0.0").unwrap());
+
+    assert_eq!(VersionProtocol::from_str(">=1.2.3 <2.0.0").unwrap(), exp);
+    assert_eq!(exp.to_string(), ">=1.2.3 <2.0.0")
+}
+
+#[test]
+fn range_with_or() {
+    let exp = VersionProtocol::Range(Range::parse("^1.2 || 2.x").unwrap());
+
+    assert_eq!(VersionProtocol::from_str("^1.2 || 2.x").unwrap(), exp);
+    assert_eq!(exp.to_string(), "^1.2 || 2.x")
+}
+
+#[test]
+fn xrange() {
+    let exp = VersionProtocol::Requirement(Range::parse("1.x").unwrap());
+
+    assert_eq!(VersionProtocol::from_str("1.x").unwrap(), exp);
+    assert_eq!(exp.to_string(), "1.x")
+}
+
+#[test]
+fn version() {
+    let exp = VersionProtocol::Version(Version::new(1, 2, 3));
+
+    assert_eq!(VersionProtocol::from_str("1.2.3").unwrap(), exp);
+    assert_eq!(exp.to_string(), "1.2.3")
+}
+
+#[test]
+fn version_with_prerelease() {
+    let exp = VersionProtocol::Version(Version::parse("1.0.0-beta.1").unwrap());
+
+    assert_eq!(VersionProtocol::from_str("1.0.0-beta.1").unwrap(), exp);
+    assert_eq!(exp.to_string(), "1.0.0-beta.1")
+}
+
+#[test]
+fn round_trips_through_display() {
+    for value in [
+        "*",
+        "npm:react",
+        "npm:react@^17.0.2",
+        "npm:@scope/pkg@next",
+        "catalog:",
+        "catalog:react18",
+        "user/repo",
+        "user/repo#semver:^1.0",
+        "git+https://github.com/user/repo.git#v1.0.0",
+        "gitlab:user/repo",
+        "jsr:@std/path@^1",
+        "patch:pkg@npm%3A1.2.3#./patches/pkg.patch",
+        "workspace:^",
+        "^1.2 || 2.x",
+        "1.2.3",
+        "latest",
+    ] {
+        let protocol = VersionProtocol::from_str(value).unwrap();
+
+        assert_eq!(protocol.to_string(), value);
+        assert_eq!(
+            VersionProtocol::from_str(&protocol.to_string()).unwrap(),
+            protocol
+        );
+    }
 }
@@ -5,7 +5,7 @@ pub mod yarn;
 
 use rustc_hash::FxHashMap;
 
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum PackageManager {
     Bun,
     Npm,
@@ -7,9 +7,28 @@ use serde_yaml::{Error, Value};
 #[derive(Clone, Debug, Default, Deserialize)]
 #[serde(default, rename_all = "camelCase")]
 pub struct PnpmWorkspaceYaml {
+    pub catalog: FxHashMap<String, String>,
+    pub catalogs: FxHashMap<String, FxHashMap<String, String>>,
     pub packages: Vec<String>,
 }
 
+impl PnpmWorkspaceYaml {
+    // https://pnpm.io/catalogs
+    pub fn get_catalog(&self, name: Option<&str>) -> Option<&FxHashMap<String, String>> {
+        match name {
+            // The default catalog can be defined in either field
+            None | Some("default") => {
+                if self.catalog.is_empty() {
+                    self.catalogs.get("default")
+                } else {
+                    Some(&self.catalog)
+                }
+            }
+            Some(name) => self.catalogs.get(name),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct PnpmLockPackage {
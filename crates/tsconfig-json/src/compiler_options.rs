@@ -15,6 +15,7 @@ pub type CompilerOptionsPathsMap = IndexMap<String, Vec<PathOrGlob>, BuildHasher
 // https://www.typescriptlang.org/tsconfig#compilerOptions
 #[derive(Clone, Debug, Default, Deserialize, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CompilerOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1,5 +1,7 @@
 mod compiler_options;
 mod path_types;
+#[cfg(feature = "schema")]
+mod schema;
 mod tsconfig_json;
 
 pub use compiler_options::*;
@@ -0,0 +1,158 @@
+use crate::compiler_options::*;
+use crate::path_types::PathOrGlob;
+use schemars::gen::SchemaGenerator;
+use schemars::schema::{
+    InstanceType, Metadata, Schema, SchemaObject, StringValidation, SubschemaValidation,
+};
+use schemars::JsonSchema;
+
+fn string_schema(description: Option<&str>, pattern: Option<&str>) -> SchemaObject {
+    SchemaObject {
+        instance_type: Some(InstanceType::String.into()),
+        metadata: description.map(|description| {
+            Box::new(Metadata {
+                description: Some(description.to_owned()),
+                ..Default::default()
+            })
+        }),
+        string: pattern.map(|pattern| {
+            Box::new(StringValidation {
+                pattern: Some(pattern.to_owned()),
+                ..Default::default()
+            })
+        }),
+        ..Default::default()
+    }
+}
+
+// TypeScript matches these options case-insensitively, and so do our
+// deserializers. The canonical casings are listed for editor completion,
+// while the pattern accepts every casing of them and of the extra aliases.
+fn case_insensitive_enum_schema(description: &str, canonical: &[&str], aliases: &[&str]) -> Schema {
+    let pattern = canonical
+        .iter()
+        .chain(aliases)
+        .map(|value| {
+            value
+                .chars()
+                .map(|ch| {
+                    if ch.is_ascii_alphabetic() {
+                        format!("[{}{}]", ch.to_ascii_uppercase(), ch.to_ascii_lowercase())
+                    } else {
+                        ch.to_string()
+                    }
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("|");
+
+    let mut enum_schema = string_schema(None, None);
+    enum_schema.enum_values = Some(canonical.iter().map(|value| (*value).into()).collect());
+
+    let mut schema = string_schema(Some(description), None);
+    schema.subschemas = Some(Box::new(SubschemaValidation {
+        any_of: Some(vec![
+            enum_schema.into(),
+            string_schema(None, Some(&format!("^({pattern})$"))).into(),
+        ]),
+        ..Default::default()
+    }));
+    schema.into()
+}
+
+impl JsonSchema for JsxField {
+    fn schema_name() -> String {
+        "JsxField".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        case_insensitive_enum_schema(
+            "How JSX is emitted.",
+            &[
+                "preserve",
+                "react",
+                "react-jsx",
+                "react-jsxdev",
+                "react-native",
+            ],
+            &["reactjsx", "reactjsxdev", "reactnative"],
+        )
+    }
+}
+
+impl JsonSchema for ModuleField {
+    fn schema_name() -> String {
+        "ModuleField".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        case_insensitive_enum_schema(
+            "The module system of emitted code.",
+            &[
+                "None", "CommonJS", "AMD", "UMD", "System", "ES6", "ES2015", "ES2020", "ES2022",
+                "ESNext", "Node16", "NodeNext", "Preserve",
+            ],
+            &["cjs", "esm", "node12"],
+        )
+    }
+}
+
+impl JsonSchema for ModuleDetectionField {
+    fn schema_name() -> String {
+        "ModuleDetectionField".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        case_insensitive_enum_schema(
+            "How files are detected as modules.",
+            &["auto", "legacy", "force"],
+            &[],
+        )
+    }
+}
+
+impl JsonSchema for ModuleResolutionField {
+    fn schema_name() -> String {
+        "ModuleResolutionField".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        case_insensitive_enum_schema(
+            "The strategy used to resolve module specifiers.",
+            &["Classic", "Node", "Node10", "Node16", "NodeNext", "Bundler"],
+            &["node12"],
+        )
+    }
+}
+
+impl JsonSchema for TargetField {
+    fn schema_name() -> String {
+        "TargetField".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        case_insensitive_enum_schema(
+            "The language version of emitted code.",
+            &[
+                "ES3", "ES5", "ES6", "ES2015", "ES2016", "ES2017", "ES2018", "ES2019", "ES2020",
+                "ES2021", "ES2022", "ESNext",
+            ],
+            &["es7"],
+        )
+    }
+}
+
+impl JsonSchema for PathOrGlob {
+    fn schema_name() -> String {
+        "PathOrGlob".into()
+    }
+
+    fn json_schema(_gen: &mut SchemaGenerator) -> Schema {
+        string_schema(
+            Some("A free-form file path, treated as a glob when it contains `*` or `?`. May start with `${configDir}`."),
+            None,
+        )
+        .into()
+    }
+}
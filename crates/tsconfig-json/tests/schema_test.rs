@@ -0,0 +1,86 @@
+use regex::Regex;
+use schemars::schema_for;
+use serde_json::{json, Value};
+use typescript_tsconfig_json::{CompilerOptions, TsConfigJson};
+
+#[test]
+fn generates_tsconfig_schema() {
+    let schema = serde_json::to_value(schema_for!(TsConfigJson)).unwrap();
+
+    assert_eq!(schema["title"], "TsConfigJson");
+
+    for key in [
+        "compilerOptions",
+        "extends",
+        "files",
+        "include",
+        "references",
+    ] {
+        assert!(schema["properties"].get(key).is_some(), "{key}");
+    }
+
+    assert_eq!(
+        schema["definitions"]["PathOrGlob"]["type"],
+        Value::String("string".into())
+    );
+}
+
+#[test]
+fn generates_compiler_options_schema() {
+    let schema = serde_json::to_value(schema_for!(CompilerOptions)).unwrap();
+
+    assert_eq!(schema["title"], "CompilerOptions");
+    assert!(schema["properties"].get("moduleResolution").is_some());
+    assert!(
+        schema["definitions"]["ModuleResolutionField"]["anyOf"][0]["enum"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("NodeNext"))
+    );
+    assert!(schema["definitions"]["JsxField"]["anyOf"][0]["enum"]
+        .as_array()
+        .unwrap()
+        .contains(&json!("react-jsx")));
+}
+
+fn enum_pattern(schema: &Value, name: &str) -> Regex {
+    Regex::new(
+        schema["definitions"][name]["anyOf"][1]["pattern"]
+            .as_str()
+            .unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn matches_enum_values_case_insensitively() {
+    let schema = serde_json::to_value(schema_for!(CompilerOptions)).unwrap();
+
+    let module = enum_pattern(&schema, "ModuleField");
+
+    for value in [
+        "ESNext", "esnext", "CommonJS", "commonjs", "cjs", "ESM", "NodeNext",
+    ] {
+        assert!(module.is_match(value), "{value}");
+    }
+
+    assert!(!module.is_match("es2021"));
+    assert!(!module.is_match("ESNext2"));
+
+    let target = enum_pattern(&schema, "TargetField");
+
+    for value in ["ESNext", "esnext", "ES2022", "es7"] {
+        assert!(target.is_match(value), "{value}");
+    }
+
+    assert!(!target.is_match("es1"));
+
+    let jsx = enum_pattern(&schema, "JsxField");
+
+    for value in ["react-jsx", "React-JSX", "reactjsx", "Preserve"] {
+        assert!(jsx.is_match(value), "{value}");
+    }
+
+    assert!(enum_pattern(&schema, "ModuleResolutionField").is_match("Bundler"));
+    assert!(enum_pattern(&schema, "ModuleDetectionField").is_match("Force"));
+}